@@ -1,7 +1,6 @@
 use std::{collections::{HashMap, HashSet, VecDeque}, fs};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 struct Trailmap {
     paths: HashMap<(i32, i32), u32>,
@@ -45,6 +44,7 @@ impl Trailmap {
 }
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     let map = get_map()?;
 
     _ = part1(&map);
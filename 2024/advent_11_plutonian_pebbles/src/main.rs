@@ -1,22 +1,30 @@
 use std::{collections::HashMap, fs, vec};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Param, Params};
+
+const PARAMS: [Param; 2] = [
+    Param::new("part1_blinks", "25", Check::NonNegative),
+    Param::new("part2_blinks", "75", Check::NonNegative),
+];
 
 fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    params.print();
+
     let input = fs::read_to_string("input.txt")?;
     let stones = input.split(" ").map(|n| (n.parse().unwrap(), 1)).collect::<HashMap<usize, usize>>();
 
-    _ = part1(&stones);
-    _ = part2(&stones);
+    _ = part1(&stones, params.get("part1_blinks")?);
+    _ = part2(&stones, params.get("part2_blinks")?);
 
     Ok(())
 }
 
-fn part1(stones: &HashMap<usize, usize>) -> Result<()> {
+fn part1(stones: &HashMap<usize, usize>, blinks: usize) -> Result<()> {
     let mut stones = stones.clone();
 
-    for _ in 0..25 {
+    for _ in 0..blinks {
         let mut blink_upd = HashMap::new();
         for (stone, count) in stones {
             let splits = split_stone(stone);
@@ -33,10 +41,10 @@ fn part1(stones: &HashMap<usize, usize>) -> Result<()> {
     return Ok(());
 }
 
-fn part2(stones: &HashMap<usize, usize>) -> Result<()> {
+fn part2(stones: &HashMap<usize, usize>, blinks: usize) -> Result<()> {
     let mut stones = stones.clone();
 
-    for _ in 0..75 {
+    for _ in 0..blinks {
         let mut blink_upd = HashMap::new();
         for (stone, count) in stones {
             let splits = split_stone(stone);
@@ -1,7 +1,6 @@
 use std::{collections::{HashMap, HashSet}, fs};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 struct Garden {
     map: HashMap<(i32, i32), char>
@@ -9,6 +8,7 @@ struct Garden {
 
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     let garden = get_garden_map()?;
 
     _ = part1(&garden);
@@ -0,0 +1,291 @@
+use std::fs;
+
+pub use aoc_core::{Error, Result};
+use aoc_core::{parsing::{parse_at, split_pair}, Check, Param, Params};
+
+const PARAMS: [Param; 3] = [
+    Param::new("button_a_cost", "3", Check::NonNegative),
+    Param::new("button_b_cost", "1", Check::NonNegative),
+    Param::new("target_delta", "10000000000000", Check::Integer),
+];
+
+#[derive(Clone, Copy)]
+struct Costs {
+    button_a: i64,
+    button_b: i64,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Machine {
+        button_a: (i64, i64),
+        button_b: (i64, i64),
+        target: (i64, i64),
+}
+
+impl Machine {
+    fn get_with_target_delta(&self, delta: i64) -> Result<Machine> {
+        let target_x = self.target.0.checked_add(delta).ok_or("Prize X coordinate overflows i64")?;
+        let target_y = self.target.1.checked_add(delta).ok_or("Prize Y coordinate overflows i64")?;
+
+        Ok(Machine { 
+            button_a: self.button_a, 
+            button_b: self.button_b, 
+            target: (target_x, target_y) 
+        })
+    }
+
+    fn get_optimal_win_cost(&self, costs: Costs) -> Result<Option<i64>> {
+        let overflow = || format!("Solving machine {:?} overflows i64", self.target);
+
+        let a_det = self.button_a.0.checked_mul(self.button_b.1)
+            .zip(self.button_a.1.checked_mul(self.button_b.0))
+            .and_then(|(l, r)| l.checked_sub(r))
+            .ok_or_else(overflow)?;
+        let a2_det = self.button_a.0.checked_mul(self.target.1)
+            .zip(self.button_a.1.checked_mul(self.target.0))
+            .and_then(|(l, r)| l.checked_sub(r))
+            .ok_or_else(overflow)?;
+
+        if a_det == 0 {
+            return Err(format!("Machine {:?}: buttons A {:?} and B {:?} move along the same line, so the presses have no single solution",
+                self.target, self.button_a, self.button_b).into());
+        }
+
+        let but_b_count = a2_det.checked_div(a_det).ok_or_else(overflow)?;
+        // The determinant isn't zero, so button A moves along at least one of the axes
+        let (target, step_a, step_b) = match self.button_a.0 {
+            0 => (self.target.1, self.button_a.1, self.button_b.1),
+            _ => (self.target.0, self.button_a.0, self.button_b.0),
+        };
+        let but_a_count = step_b.checked_mul(but_b_count)
+            .and_then(|b| target.checked_sub(b))
+            .and_then(|rest| rest.checked_div(step_a))
+            .ok_or_else(overflow)?;
+
+        let reach = |a: i64, b: i64| a.checked_mul(but_a_count)
+            .zip(b.checked_mul(but_b_count))
+            .and_then(|(l, r)| l.checked_add(r));
+        let reach_x = reach(self.button_a.0, self.button_b.0).ok_or_else(overflow)?;
+        let reach_y = reach(self.button_a.1, self.button_b.1).ok_or_else(overflow)?;
+
+        if reach_x == self.target.0 && reach_y == self.target.1 {
+            let cost = reach(costs.button_a, costs.button_b).ok_or_else(overflow)?;
+            return Ok(Some(cost));
+        }
+
+        Ok(None)
+    }
+}
+
+fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    params.print();
+    let costs = Costs { button_a: params.get("button_a_cost")?, button_b: params.get("button_b_cost")? };
+
+    let machines = parse_machines(&fs::read_to_string("input.txt")?)?;
+
+    part1(machines.clone(), costs)?;
+    part2(machines, costs, params.get("target_delta")?)
+}
+
+fn part1(machines: Vec<Machine>, costs: Costs) -> Result<()> {
+    let mut result = 0;
+
+    for machine in machines {
+        if let Some(cost) = machine.get_optimal_win_cost(costs)? {
+            result = checked_total(result, cost)?;
+        }
+    }
+
+    println!("Part 1 answer: {}", result);
+    return Ok(());
+}
+
+fn part2(machines: Vec<Machine>, costs: Costs, target_delta: i64) -> Result<()> {
+    let mut result = 0;
+
+    for machine in machines {
+        if let Some(cost) = machine.get_with_target_delta(target_delta)?.get_optimal_win_cost(costs)? {
+            result = checked_total(result, cost)?;
+        }
+    }
+
+    println!("Part 2 answer: {}", result);
+    return Ok(());
+}
+
+fn checked_total(total: i64, cost: i64) -> Result<i64> {
+    total.checked_add(cost).ok_or_else(|| "Total token cost overflows i64".into())
+}
+
+fn parse_machines(input: &str) -> Result<Vec<Machine>> {
+    let mut machines = Vec::new();
+    let mut machine = Machine::default();
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.starts_with("Button A") {
+            machine.button_a = coordinates(line, "+", line_no)?;
+        } else if line.starts_with("Button B") {
+            machine.button_b = coordinates(line, "+", line_no)?;
+        } else if line.starts_with("Prize"){
+            machine.target = coordinates(line, "=", line_no)?;
+
+            machines.push(machine);
+            machine = Machine::default();
+        }
+    }
+
+    Ok(machines)
+}
+
+/// The two numbers of `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400`, each after `sign`.
+fn coordinates(line: &str, sign: &str, line_no: usize) -> Result<(i64, i64)> {
+    let (left, right) = split_pair(line, ",", line_no)?;
+    let x = parse_at::<i64>(split_pair(left, sign, line_no)?.1, line_no)?;
+    let y = parse_at::<i64>(split_pair(right, sign, line_no)?.1, line_no)?;
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COSTS: Costs = Costs { button_a: 3, button_b: 1 };
+
+    #[test]
+    fn prize_near_i64_max_is_solved() {
+        let far = i64::MAX / 4;
+        let machine = Machine { button_a: (1, 0), button_b: (0, 1), target: (far, far) };
+        assert_eq!(machine.get_optimal_win_cost(COSTS).unwrap(), Some(far * 4));
+    }
+
+    #[test]
+    fn cost_past_i64_max_is_an_error() {
+        let machine = Machine { button_a: (1, 0), button_b: (0, 1), target: (i64::MAX / 2, 0) };
+        assert!(machine.get_optimal_win_cost(COSTS).is_err());
+
+        let machine = Machine { button_a: (i64::MAX, 2), button_b: (2, i64::MAX), target: (1, 1) };
+        assert!(machine.get_optimal_win_cost(COSTS).is_err());
+    }
+
+    #[test]
+    fn target_delta_past_i64_max_is_an_error() {
+        let machine = Machine { button_a: (1, 0), button_b: (0, 1), target: (i64::MAX - 1, 0) };
+        assert!(machine.get_with_target_delta(1).is_ok());
+        assert!(machine.get_with_target_delta(2).is_err());
+    }
+
+    #[test]
+    fn button_a_without_x_movement() {
+        let machine = Machine { button_a: (0, 2), button_b: (3, 1), target: (9, 7) };
+        assert_eq!(machine.get_optimal_win_cost(COSTS).unwrap(), Some(9));
+    }
+
+    #[test]
+    fn collinear_buttons_are_reported() {
+        let machine = Machine { button_a: (1, 1), button_b: (2, 2), target: (10, 10) };
+        let err = machine.get_optimal_win_cost(COSTS).unwrap_err();
+        assert!(err.to_string().contains("same line"), "{}", err);
+    }
+
+    fn parse_error(input: &str) -> String {
+        parse_machines(input).err().expect("input should be rejected").to_string()
+    }
+
+    #[test]
+    fn parses_machines() {
+        let machines = parse_machines("Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=9223372036854775807, Y=12176\n").unwrap();
+        assert_eq!(machines.len(), 2);
+        assert_eq!((machines[0].button_a, machines[0].button_b, machines[0].target), ((94, 34), (22, 67), (8400, 5400)));
+        assert_eq!(machines[1].target, (i64::MAX, 12176));
+    }
+
+    #[test]
+    fn numbers_past_i64_max_are_parse_errors() {
+        assert_eq!(parse_error("Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=9223372036854775808, Y=5400\n"),
+            "line 3: can't parse '9223372036854775808': number too large to fit in target type");
+        assert_eq!(parse_error("Button A: X+94, Y+99999999999999999999\n"),
+            "line 1: can't parse '99999999999999999999': number too large to fit in target type");
+    }
+
+    #[test]
+    fn malformed_lines_are_parse_errors() {
+        assert_eq!(parse_error("Button A: X+94 Y+34\n"), "line 1: expected ',' in 'Button A: X+94 Y+34'");
+        assert_eq!(parse_error("Button A: X+94, Y+34\nButton B: X22, Y+67\n"), "line 2: expected '+' in 'Button B: X22'");
+        assert!(parse_error("Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=54x0\n").starts_with("line 3: can't parse '54x0': "));
+    }
+}
+
+/*
+--- Day 13: Claw Contraption ---
+Next up: the lobby of a resort on a tropical island. The Historians take a moment to admire the hexagonal floor tiles before spreading out.
+
+Fortunately, it looks like the resort has a new arcade! Maybe you can win some prizes from the claw machines?
+
+The claw machines here are a little unusual. Instead of a joystick or directional buttons to control the claw, these machines have two buttons labeled A and B. Worse, you can't just put in a token and play; it costs 3 tokens to push the A button and 1 token to push the B button.
+
+With a little experimentation, you figure out that each machine's buttons are configured to move the claw a specific amount to the right (along the X axis) and a specific amount forward (along the Y axis) each time that button is pressed.
+
+Each machine contains one prize; to win the prize, the claw must be positioned exactly above the prize on both the X and Y axes.
+
+You wonder: what is the smallest number of tokens you would have to spend to win as many prizes as possible? You assemble a list of every machine's button behavior and prize location (your puzzle input). For example:
+
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+This list describes the button configuration and prize location of four different claw machines.
+
+For now, consider just the first claw machine in the list:
+
+Pushing the machine's A button would move the claw 94 units along the X axis and 34 units along the Y axis.
+Pushing the B button would move the claw 22 units along the X axis and 67 units along the Y axis.
+The prize is located at X=8400, Y=5400; this means that from the claw's initial position, it would need to move exactly 8400 units along the X axis and exactly 5400 units along the Y axis to be perfectly aligned with the prize in this machine.
+The cheapest way to win the prize is by pushing the A button 80 times and the B button 40 times. This would line up the claw along the X axis (because 80*94 + 40*22 = 8400) and along the Y axis (because 80*34 + 40*67 = 5400). Doing this would cost 80*3 tokens for the A presses and 40*1 for the B presses, a total of 280 tokens.
+
+For the second and fourth claw machines, there is no combination of A and B presses that will ever win a prize.
+
+For the third claw machine, the cheapest way to win the prize is by pushing the A button 38 times and the B button 86 times. Doing this would cost a total of 200 tokens.
+
+So, the most prizes you could possibly win is two; the minimum tokens you would have to spend to win all (two) prizes is 480.
+
+You estimate that each button would need to be pressed no more than 100 times to win a prize. How else would someone be expected to play?
+
+Figure out how to win as many prizes as possible. What is the fewest tokens you would have to spend to win all possible prizes?
+
+--- Part Two ---
+As you go to win the first prize, you discover that the claw is nowhere near where you expected it would be. Due to a unit conversion error in your measurements, the position of every prize is actually 10000000000000 higher on both the X and Y axis!
+
+Add 10000000000000 to the X and Y position of every prize. After making this change, the example above would now look like this:
+
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=10000000008400, Y=10000000005400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=10000000012748, Y=10000000012176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=10000000007870, Y=10000000006450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=10000000018641, Y=10000000010279
+Now, it is only possible to win a prize on the second and fourth claw machines. Unfortunately, it will take many more than 100 presses to do so.
+
+Using the corrected prize coordinates, figure out how to win as many prizes as possible. What is the fewest tokens you would have to spend to win all possible prizes?
+
+ */
@@ -1,7 +1,13 @@
-use std::{fs, str::FromStr};
+use std::fs;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Param, Params};
+
+const PARAMS: [Param; 3] = [
+    Param::new("cols", "11", Check::Positive),
+    Param::new("rows", "7", Check::Positive),
+    Param::new("seconds", "100", Check::NonNegative),
+];
 
 struct Map {
     robots: Vec<Robot>,
@@ -15,10 +21,8 @@ struct Robot {
     velocity: (i32, i32),
 }
 
-impl FromStr for Map {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
+impl Map {
+    fn parse(s: &str, cols: i32, rows: i32) -> Result<Self> {
         let robots = s.lines().map(| line | {
             let (left, right) = line.split_once(' ').unwrap();
 
@@ -31,12 +35,10 @@ impl FromStr for Map {
             Robot { pos, velocity }
         }).collect();
 
-        Ok(Map { robots, cols: 11, rows: 7 })
+        Ok(Map { robots, cols, rows })
     }
-}
 
-impl Map {
-    fn move_x(mut self, seconds: u32, print: bool) -> usize {
+    fn move_x(&mut self, seconds: u32, print: bool) -> usize {
         
         for s in 0..seconds {
 
@@ -46,29 +48,30 @@ impl Map {
             }
 
             if print {
-                self.display_grid(s);
+                println!("Seconds {}", s);
+                println!("{}", self.render_grid());
             }
         }
 
         self.get_safety_factor()
     }
 
-    fn display_grid(&self, s: u32) {
-        println!("Seconds {}", s);
+    /// Draws robot counts per tile the way the puzzle text does, `.` for empty tiles.
+    fn render_grid(&self) -> String {
+        let mut out = String::new();
         for row in 0..self.rows {
             for col in 0..self.cols {
-                if self.robots.iter().find(|r| r.pos == (col, row)).is_some() {
-                    print!("#");
-                } else {
-                    print!(".")
-                }
+                let count = self.robots.iter().filter(|r| r.pos == (col, row)).count();
+                out.push(match count {
+                    0 => '.',
+                    _ => char::from_digit(count as u32, 10).unwrap_or('*'),
+                });
             }
-            println!();
+            out.push('\n');
         }
-    
-        println!();
+        out
     }
-    
+
     fn move_robot(&self, mut robot: Robot) -> Robot {
 
         let new_pos = (robot.pos.0 + robot.velocity.0, robot.pos.1 + robot.velocity.1); 
@@ -125,16 +128,22 @@ impl Map {
 }
 
 fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    params.print();
+
     let input = fs::read_to_string("input.txt")?;
-    let map = Map::from_str(&input)?;
+    let map = Map::parse(&input, params.get("cols")?, params.get("rows")?)?;
 
-    _ = part1(map);
+    _ = part1(map, params.get("seconds")?, aoc_core::params::has_flag("--render"));
 
     Ok(())
 }
 
-fn part1(map: Map) -> Result<()> {
-    let factor= map.move_x(100, false);
+fn part1(mut map: Map, seconds: u32, render: bool) -> Result<()> {
+    let factor= map.move_x(seconds, false);
+    if render {
+        print!("{}", map.render_grid());
+    }
 
     println!("Part 1 answer: {}", factor);
     return Ok(());
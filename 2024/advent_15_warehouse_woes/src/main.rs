@@ -1,7 +1,6 @@
 use std::{collections::{HashMap, HashSet}, fs, str::FromStr, vec};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 #[derive(PartialEq, Eq, Copy, Clone)]
 enum Object {
@@ -152,21 +151,40 @@ impl Map {
         }
     }
 
-   fn print_wide(&self) {
-    for row in 0..self.wide_mapsize.0 + 1 {
-        for col in 0..self.wide_mapsize.1 + 1 {
-
-            match self.wide_objects.get(&(row, col)) {
-                Some(Object::Box) => print!("O"),
-                Some(Object::Wall) => print!("#"),
-                Some(Object::BoxLeft) => print!("["),
-                Some(Object::BoxRight) => print!("]"),
-                None if self.wide_robot == (row, col) => print!("@"),
-                None => print!("."),
+   fn gps_sum(objects: &HashMap<(i32, i32), Object>, kind: Object) -> Result<i32> {
+        let mut sum: i32 = 0;
+        for ((row, col), _) in objects.iter().filter(|(_, obj)| **obj == kind) {
+            let gps = row.checked_mul(100).and_then(|r| r.checked_add(*col))
+                .ok_or_else(|| format!("GPS coordinate of box at ({}, {}) overflows i32", row, col))?;
+            sum = sum.checked_add(gps).ok_or("Sum of GPS coordinates overflows i32")?;
+        }
+        Ok(sum)
+   }
+
+   fn render(&self) -> String {
+        Map::render_objects(&self.objects, self.robot, self.mapsize)
+   }
+
+   fn render_wide(&self) -> String {
+        Map::render_objects(&self.wide_objects, self.wide_robot, self.wide_mapsize)
+   }
+
+   fn render_objects(objects: &HashMap<(i32, i32), Object>, robot: (i32, i32), mapsize: (i32, i32)) -> String {
+        let mut out = String::new();
+        for row in 0..mapsize.0 + 1 {
+            for col in 0..mapsize.1 + 1 {
+                out.push(match objects.get(&(row, col)) {
+                    Some(Object::Box) => 'O',
+                    Some(Object::Wall) => '#',
+                    Some(Object::BoxLeft) => '[',
+                    Some(Object::BoxRight) => ']',
+                    None if robot == (row, col) => '@',
+                    None => '.',
+                });
             }
+            out.push('\n');
         }
-        println!();
-    }
+        out
    }
 }
 
@@ -226,43 +244,61 @@ impl FromStr for Map {
 }
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
+    let render = aoc_core::params::has_flag("--render");
     let input = fs::read_to_string("input.txt")?;
     let map = Map::from_str(&input)?;
 
-    _ = part1(map.clone());
-    _ = part2(map);
-
-    Ok(())
+    part1(map.clone(), render)?;
+    part2(map, render)
 }
 
-fn part1(mut map: Map) -> Result<()> {
+fn part1(mut map: Map, render: bool) -> Result<()> {
     map.walk();
+    if render {
+        print!("{}", map.render());
+    }
 
-    let result = map.objects.iter().filter_map(|((row, col), obj)| {
-        if obj == &Object::Box {
-            return Some(row * 100 + col )
-        }
-        None
-    }).sum::<i32>();
+    let result = Map::gps_sum(&map.objects, Object::Box)?;
 
     println!("Part 1 answer: {}", result);
     return Ok(());
 }
 
-fn part2(mut map: Map) -> Result<()> {
+fn part2(mut map: Map, render: bool) -> Result<()> {
     map.walk_wide();
+    if render {
+        print!("{}", map.render_wide());
+    }
 
-    let result = map.wide_objects.iter().filter_map(|((row, col), obj)| {
-        if obj == &Object::BoxLeft {
-            return Some(row * 100 + col )
-        }
-        None
-    }).sum::<i32>();
-    map.print_wide();
+    let result = Map::gps_sum(&map.wide_objects, Object::BoxLeft)?;
     println!("Part 2 answer: {}", result);
     return Ok(());
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gps_sum_near_i32_max() {
+        let row = i32::MAX / 100;
+        let objects = HashMap::from([((row, 47), Object::Box), ((0, 1), Object::Wall)]);
+        assert_eq!(Map::gps_sum(&objects, Object::Box).unwrap(), row * 100 + 47);
+
+        let objects = HashMap::from([((row, 48), Object::Box)]);
+        assert!(Map::gps_sum(&objects, Object::Box).is_err());
+    }
+
+    #[test]
+    fn gps_sum_past_i32_max_is_an_error() {
+        let row = i32::MAX / 200;
+        let objects = HashMap::from([((row, 0), Object::BoxLeft), ((row + 1, 0), Object::BoxLeft)]);
+        assert!(Map::gps_sum(&objects, Object::BoxLeft).is_err());
+        assert_eq!(Map::gps_sum(&objects, Object::Box).unwrap(), 0);
+    }
+}
+
 /*
 --- Day 15: Warehouse Woes ---
 You appear back inside your own mini submarine! Each Historian drives their mini submarine in a different direction; maybe the Chief has his own submarine down here somewhere as well?
@@ -2,8 +2,12 @@ use std::{collections::{HashMap, HashSet}, fs, str::FromStr};
 
 use pathfinding::prelude::astar_bag_collect;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Param, Params};
+
+const PARAMS: [Param; 1] = [
+    Param::new("turn_cost", "1000", Check::NonNegative),
+];
 
 #[derive(PartialEq, Eq)]
 enum Type {
@@ -17,6 +21,7 @@ struct Maze {
     start: (i32, i32),
     end: (i32, i32),
     dir: (i32, i32),
+    turn_cost: u32,
 }
 
 impl FromStr for Maze {
@@ -65,10 +70,10 @@ impl Maze {
                 }
     
                 let next_dir_cl = Maze::turn_clockwise(*curr_dir);
-                successors.push(((*curr_pos, next_dir_cl), 1000));
+                successors.push(((*curr_pos, next_dir_cl), self.turn_cost));
         
                 let next_dir_ant = Maze::turn_anti_clockwise(*curr_dir);
-                successors.push(((*curr_pos, next_dir_ant), 1000));
+                successors.push(((*curr_pos, next_dir_ant), self.turn_cost));
     
                 successors.into_iter()
             },
@@ -78,6 +83,25 @@ impl Maze {
             |(curr_pos, _)| *curr_pos == self.end).unwrap_or_default()
     }
 
+    /// Draws the maze with every tile that lies on some best path marked `O`.
+    fn render_best_tiles(&self, tiles: &HashSet<(i32, i32)>) -> String {
+        let rows = self.map.keys().map(|(row, _)| *row).max().unwrap_or(-1) + 1;
+        let cols = self.map.keys().map(|(_, col)| *col).max().unwrap_or(-1) + 1;
+
+        let mut out = String::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                out.push(match self.map.get(&(row, col)) {
+                    _ if tiles.contains(&(row, col)) => 'O',
+                    Some(Type::Wall) => '#',
+                    _ => '.',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+
     fn turn_clockwise(dir: (i32, i32)) -> (i32, i32) {
         match dir {
             //North -> East
@@ -108,12 +132,21 @@ impl Maze {
 }
 
 fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    params.print();
+
     let input = fs::read_to_string("input.txt")?;
-    let maze = Maze::from_str(input.as_str())?;
+    let mut maze = Maze::from_str(input.as_str())?;
+    maze.turn_cost = params.get("turn_cost")?;
     let result = maze.find_paths_to_end();
 
+    let best_tiles = result.0.iter().flat_map(|x| x.iter().map(|x| x.0)).collect::<HashSet<(i32, i32)>>();
+
     println!("Part 1 answer: {}", &result.1);
-    println!("Part 2 answer: {}", &result.0.iter().flat_map(|x| x.iter().map(|x| x.0)).collect::<HashSet<(i32, i32)>>().len());
+    if aoc_core::params::has_flag("--render") {
+        print!("{}", maze.render_best_tiles(&best_tiles));
+    }
+    println!("Part 2 answer: {}", best_tiles.len());
 
     Ok(())
 }
@@ -0,0 +1,256 @@
+mod metrics;
+mod report;
+
+use std::{fs, str::FromStr, time::Instant};
+
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Param, Params};
+use metrics::{ListMetric, SimilarityScore, SortedPairDistance};
+
+const PARAMS: [Param; 2] = [
+    Param::new("bench_lines", "1000000", Check::Positive),
+    Param::new("report_top", "10", Check::NonNegative),
+];
+
+/// Location ID columns of the puzzle input, in input order. The puzzle has two.
+struct LocationLists {
+    lists: Vec<Vec<u32>>,
+}
+
+impl FromStr for LocationLists {
+    type Err = Error;
+
+    /// Columns may be separated by any whitespace. Lines before the first row of numbers
+    /// where no token is a number are treated as headers, blank lines are skipped.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lists: Vec<Vec<u32>> = Vec::new();
+        for (idx, line) in s.lines().enumerate() {
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            if tokens.is_empty() {
+                continue;
+            }
+            if lists.is_empty() && tokens.iter().all(|t| t.parse::<u32>().is_err()) {
+                continue;
+            }
+
+            let row = tokens.iter().map(|t| aoc_core::parsing::parse_at::<u32>(t, idx + 1)).collect::<Result<Vec<_>>>()?;
+            if lists.is_empty() {
+                lists = vec![Vec::new(); row.len()];
+            }
+            if row.len() != lists.len() {
+                return Err(format!("line {}: expected {} columns, found {}", idx + 1, lists.len(), row.len()).into());
+            }
+
+            for (list, id) in lists.iter_mut().zip(row) {
+                list.push(id);
+            }
+        }
+
+        if lists.len() < 2 {
+            return Err("Expected at least two lists of location IDs".into());
+        }
+        Ok(LocationLists { lists })
+    }
+}
+
+impl LocationLists {
+    /// Index pairs `(a, b)` with `a < b`, in order, for comparing every list to every later one.
+    fn pairs(&self) -> Vec<(usize, usize)> {
+        (0..self.lists.len()).flat_map(|a| (a + 1..self.lists.len()).map(move |b| (a, b))).collect()
+    }
+
+    fn measure(&self, metric: &dyn ListMetric, a: usize, b: usize) -> Result<u64> {
+        metric.measure(&self.lists[a], &self.lists[b])
+    }
+
+    /// Pseudo-random lists shaped like the real input (five digit IDs), for benchmarking.
+    fn generate(lines: usize) -> LocationLists {
+        let mut seed: u64 = 0x2024_1201;
+        let mut next_id = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            10_000 + ((seed >> 33) % 90_000) as u32
+        };
+
+        let mut lists = LocationLists { lists: vec![Vec::with_capacity(lines), Vec::with_capacity(lines)] };
+        for _ in 0..lines {
+            lists.lists[0].push(next_id());
+            lists.lists[1].push(next_id());
+        }
+        lists
+    }
+}
+
+fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    if aoc_core::params::has_flag("--bench") {
+        params.print();
+        return bench(params.get("bench_lines")?);
+    }
+
+    let input = fs::read_to_string("input.txt")?;
+    let lists = LocationLists::from_str(&input)?;
+
+    part1(&lists)?;
+    part2(&lists)?;
+
+    for name in aoc_core::params::values("--metric") {
+        let metric = metrics::by_name(&name)
+            .ok_or_else(|| format!("Unknown metric '{}', expected one of: {}", name, metrics::NAMES.join(", ")))?;
+        for (a, b) in lists.pairs() {
+            println!("{}{}: {}", metric.name(), pair_label(&lists, a, b), lists.measure(metric.as_ref(), a, b)?);
+        }
+    }
+
+    if aoc_core::params::has_flag("--report") {
+        report::print_report(&lists, params.get("report_top")?);
+    }
+
+    Ok(())
+}
+
+fn part1(lists: &LocationLists) -> Result<()> {
+    for (a, b) in lists.pairs() {
+        println!("Part 1 answer{}: {}", pair_label(lists, a, b), lists.measure(&SortedPairDistance, a, b)?);
+    }
+    Ok(())
+}
+
+fn part2(lists: &LocationLists) -> Result<()> {
+    for (a, b) in lists.pairs() {
+        println!("Part 2 answer{}: {}", pair_label(lists, a, b), lists.measure(&SimilarityScore, a, b)?);
+    }
+    return Ok(());
+}
+
+/// Empty for the puzzle's two lists, otherwise names the 1-based list numbers compared.
+fn pair_label(lists: &LocationLists, a: usize, b: usize) -> String {
+    match lists.lists.len() {
+        2 => String::new(),
+        _ => format!(" (lists {} and {})", a + 1, b + 1),
+    }
+}
+
+fn bench(lines: usize) -> Result<()> {
+    let lists = LocationLists::generate(lines);
+
+    let start = Instant::now();
+    let distance = lists.measure(&SortedPairDistance, 0, 1)?;
+    println!("Part 1 on {} lines: {} in {:?}", lines, distance, start.elapsed());
+
+    let start = Instant::now();
+    let similarity = lists.measure(&SimilarityScore, 0, 1)?;
+    println!("Part 2 on {} lines: {} in {:?}", lines, similarity, start.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> String {
+        input.parse::<LocationLists>().err().expect("input should be rejected").to_string()
+    }
+
+    #[test]
+    fn columns_split_on_any_whitespace() {
+        let lists = "3   4\n4\t3\n 2 \t 5 \n\n1 3\n".parse::<LocationLists>().unwrap();
+        assert_eq!(lists.lists, [vec![3, 4, 2, 1], vec![4, 3, 5, 3]]);
+    }
+
+    #[test]
+    fn any_number_of_columns() {
+        let lists = "1 2 3\n4 5 6\n".parse::<LocationLists>().unwrap();
+        assert_eq!(lists.lists, [vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(lists.pairs(), [(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn header_lines_are_skipped() {
+        let lists = "left right\n\nid id\n3 4\n4 3\n".parse::<LocationLists>().unwrap();
+        assert_eq!(lists.lists, [vec![3, 4], vec![4, 3]]);
+    }
+
+    #[test]
+    fn bad_tokens_are_reported_with_their_line() {
+        assert!(error("x1 3\n4 3\n").starts_with("line 1: can't parse 'x1': "));
+        assert!(error("left right\n3 4\n3 4x\n").starts_with("line 3: can't parse '4x': "));
+        assert!(error("3 4\nleft right\n").starts_with("line 2: can't parse 'left': "));
+    }
+
+    #[test]
+    fn rows_need_the_same_number_of_columns() {
+        assert_eq!(error("3 4\n4 3 5\n"), "line 2: expected 2 columns, found 3");
+        assert_eq!(error("header\n3\n4\n"), "Expected at least two lists of location IDs");
+    }
+}
+
+/*
+--- Day 1: Historian Hysteria ---
+The Chief Historian is always present for the big Christmas sleigh launch, but nobody has seen him in months! Last anyone heard, he was visiting locations that are historically significant to the North Pole; a group of Senior Historians has asked you to accompany them as they check the places they think he was most likely to visit.
+
+As each location is checked, they will mark it on their list with a star. They figure the Chief Historian must be in one of the first fifty places they'll look, so in order to save Christmas, you need to help them get fifty stars on their list before Santa takes off on December 25th.
+
+Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+
+You haven't even left yet and the group of Elvish Senior Historians has already hit a problem: their list of locations to check is currently empty. Eventually, someone decides that the best place to check first would be the Chief Historian's office.
+
+Upon pouring into the office, everyone confirms that the Chief Historian is indeed nowhere to be found. Instead, the Elves discover an assortment of notes and lists of historically significant locations! This seems to be the planning the Chief Historian was doing before he left. Perhaps these notes can be used to determine which locations to search?
+
+Throughout the Chief's office, the historically significant locations are listed not by name but by a unique number called the location ID. To make sure they don't miss anything, The Historians split into two groups, each searching the office and trying to create their own complete list of location IDs.
+
+There's just one problem: by holding the two lists up side by side (your puzzle input), it quickly becomes clear that the lists aren't very similar. Maybe you can help The Historians reconcile their lists?
+
+For example:
+
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+Maybe the lists are only off by a small amount! To find out, pair up the numbers and measure how far apart they are. Pair up the smallest number in the left list with the smallest number in the right list, then the second-smallest left number with the second-smallest right number, and so on.
+
+Within each pair, figure out how far apart the two numbers are; you'll need to add up all of those distances. For example, if you pair up a 3 from the left list with a 7 from the right list, the distance apart is 4; if you pair up a 9 with a 3, the distance apart is 6.
+
+In the example list above, the pairs and distances would be as follows:
+
+The smallest number in the left list is 1, and the smallest number in the right list is 3. The distance between them is 2.
+The second-smallest number in the left list is 2, and the second-smallest number in the right list is another 3. The distance between them is 1.
+The third-smallest number in both lists is 3, so the distance between them is 0.
+The next numbers to pair up are 3 and 4, a distance of 1.
+The fifth-smallest numbers in each list are 3 and 5, a distance of 2.
+Finally, the largest number in the left list is 4, while the largest number in the right list is 9; these are a distance 5 apart.
+To find the total distance between the left list and the right list, add up the distances between all of the pairs you found. In the example above, this is 2 + 1 + 0 + 1 + 2 + 5, a total distance of 11!
+
+Your actual left and right lists contain many location IDs. What is the total distance between your lists?
+
+--- Part Two ---
+Your analysis only confirmed what everyone feared: the two lists of location IDs are indeed very different.
+
+Or are they?
+
+The Historians can't agree on which group made the mistakes or how to read most of the Chief's handwriting, but in the commotion you notice an interesting detail: a lot of location IDs appear in both lists! Maybe the other numbers aren't location IDs at all but rather misinterpreted handwriting.
+
+This time, you'll need to figure out exactly how often each number from the left list appears in the right list. Calculate a total similarity score by adding up each number in the left list after multiplying it by the number of times that number appears in the right list.
+
+Here are the same example lists again:
+
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+For these example lists, here is the process of finding the similarity score:
+
+The first number in the left list is 3. It appears in the right list three times, so the similarity score increases by 3 * 3 = 9.
+The second number in the left list is 4. It appears in the right list once, so the similarity score increases by 4 * 1 = 4.
+The third number in the left list is 2. It does not appear in the right list, so the similarity score does not increase (2 * 0 = 0).
+The fourth number, 1, also does not appear in the right list.
+The fifth number, 3, appears in the right list three times; the similarity score increases by 9.
+The last number, 3, appears in the right list three times; the similarity score again increases by 9.
+So, for these example lists, the similarity score at the end of this process is 31 (9 + 4 + 0 + 0 + 9 + 9).
+
+Once again consider your left and right lists. What is their similarity score?
+*/
\ No newline at end of file
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+
+use crate::Result;
+
+/// A way of scoring how far apart two location ID lists are.
+pub trait ListMetric {
+    fn name(&self) -> &'static str;
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64>;
+}
+
+pub const NAMES: [&str; 5] = ["distance", "similarity", "symmetric-difference", "matching", "kendall-tau"];
+
+pub fn by_name(name: &str) -> Option<Box<dyn ListMetric>> {
+    match name {
+        "distance" => Some(Box::new(SortedPairDistance)),
+        "similarity" => Some(Box::new(SimilarityScore)),
+        "symmetric-difference" => Some(Box::new(SymmetricDifference)),
+        "matching" => Some(Box::new(OptimalMatching { pair_cost: squared_difference })),
+        "kendall-tau" => Some(Box::new(KendallTau)),
+        _ => None,
+    }
+}
+
+fn checked_sum(values: impl Iterator<Item = u64>, metric: &str) -> Result<u64> {
+    let mut sum: u64 = 0;
+    for value in values {
+        sum = sum.checked_add(value).ok_or_else(|| format!("{} overflows u64", metric))?;
+    }
+    Ok(sum)
+}
+
+fn counts(list: &[u32]) -> HashMap<u32, u64> {
+    let mut counts = HashMap::new();
+    for id in list {
+        *counts.entry(*id).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Part 1: pair the lists smallest to smallest and add up the differences.
+pub struct SortedPairDistance;
+
+impl ListMetric for SortedPairDistance {
+    fn name(&self) -> &'static str {
+        "distance"
+    }
+
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64> {
+        let mut left = a.to_vec();
+        let mut right = b.to_vec();
+        left.sort_unstable();
+        right.sort_unstable();
+
+        checked_sum(left.iter().zip(right.iter()).map(|(l, r)| l.abs_diff(*r) as u64), "Total distance")
+    }
+}
+
+/// Part 2: every ID of `a` weighted by how often it appears in `b`.
+pub struct SimilarityScore;
+
+impl ListMetric for SimilarityScore {
+    fn name(&self) -> &'static str {
+        "similarity"
+    }
+
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64> {
+        let right_counts = counts(b);
+
+        let mut scores = Vec::with_capacity(a.len());
+        for val in a {
+            let count = right_counts.get(val).copied().unwrap_or(0);
+            scores.push((*val as u64).checked_mul(count).ok_or_else(|| format!("Similarity score of {} * {} overflows u64", val, count))?);
+        }
+        checked_sum(scores.into_iter(), "Similarity score")
+    }
+}
+
+/// Number of IDs left over after cancelling out the ones both lists share, duplicates included.
+pub struct SymmetricDifference;
+
+impl ListMetric for SymmetricDifference {
+    fn name(&self) -> &'static str {
+        "symmetric-difference"
+    }
+
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64> {
+        let left = counts(a);
+        let right = counts(b);
+
+        let only_left = left.iter().map(|(id, n)| n.saturating_sub(right.get(id).copied().unwrap_or(0)));
+        let only_right = right.iter().map(|(id, n)| n.saturating_sub(left.get(id).copied().unwrap_or(0)));
+        checked_sum(only_left.chain(only_right), "Symmetric difference")
+    }
+}
+
+pub fn squared_difference(a: u32, b: u32) -> u64 {
+    let diff = a.abs_diff(b) as u64;
+    diff * diff
+}
+
+/// Cheapest one-to-one pairing of the two lists under `pair_cost`, found with the Hungarian
+/// algorithm in O(n³). Unpaired IDs of the longer list cost nothing.
+pub struct OptimalMatching {
+    pub pair_cost: fn(u32, u32) -> u64,
+}
+
+impl ListMetric for OptimalMatching {
+    fn name(&self) -> &'static str {
+        "matching"
+    }
+
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64> {
+        let (rows, cols) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut cost = Vec::with_capacity(rows.len());
+        for r in rows {
+            let row = cols.iter().map(|c| {
+                let pair = if a.len() <= b.len() { (self.pair_cost)(*r, *c) } else { (self.pair_cost)(*c, *r) };
+                i128::from(pair)
+            }).collect::<Vec<_>>();
+            cost.push(row);
+        }
+
+        let assignment = hungarian(&cost);
+        checked_sum(assignment.iter().enumerate().map(|(r, c)| cost[r][*c] as u64), "Matching cost")
+    }
+}
+
+/// Minimum cost assignment of every row to a distinct column, `rows <= cols`.
+/// Returns the column chosen for each row.
+fn hungarian(cost: &[Vec<i128>]) -> Vec<usize> {
+    let n = cost.len();
+    let m = cost[0].len();
+    let mut u = vec![0i128; n + 1];
+    let mut v = vec![0i128; m + 1];
+    let mut matched_row = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for row in 1..=n {
+        matched_row[0] = row;
+        let mut col0 = 0;
+        let mut min_v = vec![i128::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+
+        loop {
+            used[col0] = true;
+            let row0 = matched_row[col0];
+            let mut delta = i128::MAX;
+            let mut col1 = 0;
+            for col in 1..=m {
+                if used[col] {
+                    continue;
+                }
+                let reduced = cost[row0 - 1][col - 1] - u[row0] - v[col];
+                if reduced < min_v[col] {
+                    min_v[col] = reduced;
+                    way[col] = col0;
+                }
+                if min_v[col] < delta {
+                    delta = min_v[col];
+                    col1 = col;
+                }
+            }
+            for col in 0..=m {
+                if used[col] {
+                    u[matched_row[col]] += delta;
+                    v[col] -= delta;
+                } else {
+                    min_v[col] -= delta;
+                }
+            }
+            col0 = col1;
+            if matched_row[col0] == 0 {
+                break;
+            }
+        }
+
+        while col0 != 0 {
+            let prev = way[col0];
+            matched_row[col0] = matched_row[prev];
+            col0 = prev;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for col in 1..=m {
+        if matched_row[col] != 0 {
+            assignment[matched_row[col] - 1] = col - 1;
+        }
+    }
+    assignment
+}
+
+/// Kendall tau distance between the two orderings of the input rows: the number of row pairs
+/// that the lists put in opposite order. Ties in either list don't count. O(n log n).
+pub struct KendallTau;
+
+impl ListMetric for KendallTau {
+    fn name(&self) -> &'static str {
+        "kendall-tau"
+    }
+
+    fn measure(&self, a: &[u32], b: &[u32]) -> Result<u64> {
+        let mut rows = a.iter().zip(b.iter()).map(|(l, r)| (*l, *r)).collect::<Vec<_>>();
+        rows.sort_unstable();
+
+        // Rows are now ordered by `a`, and by `b` within equal `a`, so every strict
+        // inversion left in the `b` column is a discordant pair.
+        let mut column = rows.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+        Ok(count_inversions(&mut column))
+    }
+}
+
+fn count_inversions(values: &mut [u32]) -> u64 {
+    if values.len() < 2 {
+        return 0;
+    }
+
+    let mid = values.len() / 2;
+    let mut inversions = count_inversions(&mut values[..mid]) + count_inversions(&mut values[mid..]);
+
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < values.len() {
+        if values[i] <= values[j] {
+            merged.push(values[i]);
+            i += 1;
+        } else {
+            inversions += (mid - i) as u64;
+            merged.push(values[j]);
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..mid]);
+    merged.extend_from_slice(&values[j..]);
+    values.copy_from_slice(&merged);
+
+    inversions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small lists with plenty of repeated IDs, of every length up to `max_len`.
+    fn generated_lists(count: usize, max_len: usize) -> Vec<Vec<u32>> {
+        let mut state = 2024u64;
+        let mut below = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        (0..count).map(|_| {
+            let len = below(max_len as u64 + 1) as usize;
+            let range = 1 + below(20);
+            (0..len).map(|_| below(range) as u32).collect()
+        }).collect()
+    }
+
+    /// Every ordering of `0..n`.
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![Vec::new()];
+        }
+        let mut all = Vec::new();
+        for shorter in permutations(n - 1) {
+            for at in 0..n {
+                let mut perm = shorter.clone();
+                perm.insert(at, n - 1);
+                all.push(perm);
+            }
+        }
+        all
+    }
+
+    /// Tries every way of pairing each ID of the shorter list with a distinct ID of the other.
+    fn matching_brute_force(a: &[u32], b: &[u32], pair_cost: fn(u32, u32) -> u64) -> u64 {
+        let paired = a.len().min(b.len());
+        let longer = a.len().max(b.len());
+        permutations(longer).iter().map(|perm| {
+            (0..paired).map(|i| match a.len() <= b.len() {
+                true => pair_cost(a[i], b[perm[i]]),
+                false => pair_cost(a[perm[i]], b[i]),
+            }).sum()
+        }).min().unwrap()
+    }
+
+    #[test]
+    fn matching_is_the_cheapest_pairing() {
+        let lists = generated_lists(120, 6);
+        // Not symmetric, so pairing the lists the wrong way round shows up
+        let lopsided: fn(u32, u32) -> u64 = |a, b| a as u64 * (b as u64 % 7) + b as u64;
+        for pair in lists.chunks(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            for pair_cost in [squared_difference, lopsided] {
+                let matching = OptimalMatching { pair_cost }.measure(a, b).unwrap();
+                assert_eq!(matching, matching_brute_force(a, b, pair_cost), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn kendall_tau_counts_discordant_pairs() {
+        let lists = generated_lists(400, 12);
+        for pair in lists.chunks(2) {
+            let len = pair[0].len().min(pair[1].len());
+            let (a, b) = (&pair[0][..len], &pair[1][..len]);
+            let discordant = (0..len).flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+                .filter(|(i, j)| (a[*i] < a[*j] && b[*i] > b[*j]) || (a[*i] > a[*j] && b[*i] < b[*j]))
+                .count() as u64;
+            assert_eq!(KendallTau.measure(a, b).unwrap(), discordant, "{a:?} {b:?}");
+        }
+    }
+
+    #[test]
+    fn symmetric_difference_cancels_shared_ids_once() {
+        let lists = generated_lists(400, 12);
+        for pair in lists.chunks(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let mut left_over = b.clone();
+            let mut only_a = 0;
+            for id in a {
+                match left_over.iter().position(|other| other == id) {
+                    Some(at) => _ = left_over.swap_remove(at),
+                    None => only_a += 1,
+                }
+            }
+            assert_eq!(SymmetricDifference.measure(a, b).unwrap(), only_a + left_over.len() as u64, "{a:?} {b:?}");
+        }
+    }
+
+    #[test]
+    fn distance_of_extreme_ids_does_not_wrap() {
+        let distance = SortedPairDistance.measure(&[u32::MAX; 3], &[0; 3]).unwrap();
+        assert_eq!(distance, 3 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn similarity_of_extreme_ids_does_not_wrap() {
+        let score = SimilarityScore.measure(&[u32::MAX; 2], &[u32::MAX; 3]).unwrap();
+        assert_eq!(score, 6 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn sum_past_u64_is_an_error() {
+        assert_eq!(checked_sum([u64::MAX - 1, 1].into_iter(), "Test").unwrap(), u64::MAX);
+        let err = checked_sum([u64::MAX - 1, 2].into_iter(), "Test").unwrap_err();
+        assert_eq!(err.to_string(), "Test overflows u64");
+    }
+}
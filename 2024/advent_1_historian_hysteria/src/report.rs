@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use crate::LocationLists;
+
+/// What the historians need to reconcile their lists: the IDs only one group wrote down,
+/// and the sorted pairs that are furthest apart.
+pub fn print_report(lists: &LocationLists, top: usize) {
+    println!("Reconciliation report");
+
+    let mut seen_in: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    for (idx, list) in lists.lists.iter().enumerate() {
+        for id in list {
+            seen_in.entry(*id).or_insert_with(|| vec![0; lists.lists.len()])[idx] += 1;
+        }
+    }
+
+    let unique = seen_in.iter()
+        .filter_map(|(id, counts)| match counts.iter().filter(|c| **c > 0).count() {
+            1 => counts.iter().position(|c| *c > 0).map(|list| (*id, list, counts[list])),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    println!("IDs found in only one list: {}", unique.len());
+    for (id, list, count) in unique {
+        println!("  {:>10}  list {} ({}x)", id, list + 1, count);
+    }
+
+    for (a, b) in lists.pairs() {
+        let mut left = lists.lists[a].clone();
+        let mut right = lists.lists[b].clone();
+        left.sort_unstable();
+        right.sort_unstable();
+
+        let mut pairs = left.into_iter().zip(right).enumerate().collect::<Vec<_>>();
+        pairs.sort_by_key(|(rank, (l, r))| (std::cmp::Reverse(l.abs_diff(*r)), *rank));
+
+        println!("Largest discrepancies between lists {} and {}:", a + 1, b + 1);
+        for (rank, (l, r)) in pairs.into_iter().take(top) {
+            println!("  #{:<6} {:>10} vs {:<10} distance {}", rank + 1, l, r, l.abs_diff(r));
+        }
+    }
+}
@@ -0,0 +1,457 @@
+use std::{collections::HashMap, fs, str::FromStr};
+
+pub use aoc_core::{Error, Result};
+use aoc_core::{sequence::{self, Slope}, Check, Param, Params};
+
+const PARAMS: [Param; 4] = [
+    Param::new("min_diff", "1", Check::NonNegative),
+    Param::new("max_diff", "3", Check::Positive),
+    Param::new("direction", "either", Check::OneOf(&["either", "increasing", "decreasing"])),
+    Param::new("dampener", "1", Check::NonNegative),
+];
+
+/// Which way the levels of a safe report may move.
+#[derive(Clone, Copy)]
+enum Trend {
+    Either,
+    Increasing,
+    Decreasing,
+}
+
+impl FromStr for Trend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "either" => Ok(Trend::Either),
+            "increasing" => Ok(Trend::Increasing),
+            "decreasing" => Ok(Trend::Decreasing),
+            _ => Err(format!("Unknown direction '{}'", s).into()),
+        }
+    }
+}
+
+impl Trend {
+    fn slopes(&self) -> &'static [Slope] {
+        match self {
+            Trend::Either => &[Slope::Increasing, Slope::Decreasing],
+            Trend::Increasing => &[Slope::Increasing],
+            Trend::Decreasing => &[Slope::Decreasing],
+        }
+    }
+}
+
+/// What makes a report safe: every step between adjacent levels is between `min_step` and
+/// `max_step` in the same direction, after the dampener drops at most `removable` levels.
+#[derive(Clone, Copy)]
+struct SafetyRule {
+    min_step: i32,
+    max_step: i32,
+    trend: Trend,
+    removable: usize,
+}
+
+impl SafetyRule {
+    fn step_ok(&self, slope: Slope, prev: i32, num: i32) -> bool {
+        let step = match slope {
+            Slope::Increasing => num as i64 - prev as i64,
+            Slope::Decreasing => prev as i64 - num as i64,
+            Slope::Flat => return false,
+        };
+        step > 0 && step >= self.min_step as i64 && step <= self.max_step as i64
+    }
+
+    /// Smallest set of level indices whose removal makes the report safe, if it takes at most
+    /// `removable` of them. Runs in O(n·k) for each allowed direction.
+    fn dampen(&self, nums: &[i32]) -> Option<Vec<usize>> {
+        let step_ok = |d, prev, num| self.step_ok(d, prev, num);
+        let kept = sequence::safe_subsequence(nums, self.trend.slopes(), step_ok, Some(self.removable))?;
+        let mut is_kept = vec![false; nums.len()];
+        for idx in kept {
+            is_kept[idx] = true;
+        }
+        Some((0..nums.len()).filter(|i| !is_kept[*i]).collect())
+    }
+
+    /// How many levels would have to go with an unlimited dampener.
+    fn min_removals(&self, nums: &[i32]) -> usize {
+        sequence::min_removals(nums, self.trend.slopes(), |d, prev, num| self.step_ok(d, prev, num))
+    }
+
+    fn verdict(&self, nums: &[i32]) -> Verdict {
+        match check_record(nums, self) {
+            Verdict::Unsafe(violation) if self.removable > 0 => match self.dampen(nums) {
+                Some(removed) => Verdict::Rescued { violation, removed },
+                None => Verdict::Unsafe(violation),
+            },
+            verdict => verdict,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    params.print();
+    let rule = SafetyRule {
+        min_step: params.get("min_diff")?,
+        max_step: params.get("max_diff")?,
+        trend: params.get("direction")?,
+        removable: params.get("dampener")?,
+    };
+
+    let input = fs::read_to_string("input.txt")?;
+    let reports = input.lines()
+        .map(|line| line.split_whitespace().map(|x| x.parse::<i32>()).collect::<std::result::Result<Vec<_>, _>>())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    part1(&reports, &rule)?;
+    part2(&reports, &rule)?;
+
+    if aoc_core::params::has_flag("--report") {
+        print!("{}", report(&reports, &rule));
+    }
+
+    Ok(())
+}
+
+fn part1(reports: &[Vec<i32>], rule: &SafetyRule) -> Result<()> {
+    let valid_records = reports.iter().filter(|nums| check_record(nums, rule).is_safe()).count();
+
+    println!("Part 1 answer: {}", valid_records);
+    Ok(())
+}
+
+fn part2(reports: &[Vec<i32>], rule: &SafetyRule) -> Result<()> {
+    let valid_records = reports.iter().filter(|nums| rule.verdict(nums).is_safe()).count();
+
+    println!("Part 2 answer: {}", valid_records);
+    return Ok(());
+}
+
+/// One line per report explaining its verdict, then how many reports failed for each reason.
+fn report(reports: &[Vec<i32>], rule: &SafetyRule) -> String {
+    let mut out = String::new();
+    let mut failures: HashMap<Failure, (usize, usize)> = HashMap::new();
+    let (mut safe, mut rescued) = (0, 0);
+
+    for nums in reports {
+        let levels = nums.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let describe = |v: &Violation| format!("{} -> {} at levels {}-{} ({})",
+            nums[v.index], nums[v.index + 1], v.index + 1, v.index + 2, v.reason.describe());
+
+        let line = match rule.verdict(nums) {
+            Verdict::Safe => {
+                safe += 1;
+                match sequence::slope(nums) {
+                    Some(Slope::Increasing) => "Safe, increasing".to_string(),
+                    Some(Slope::Decreasing) => "Safe, decreasing".to_string(),
+                    _ => "Safe".to_string(),
+                }
+            }
+            Verdict::Unsafe(violation) => {
+                failures.entry(violation.reason).or_default().0 += 1;
+                format!("Unsafe, {}; runs {}, needs {} removals", describe(&violation), runs(nums), rule.min_removals(nums))
+            }
+            Verdict::Rescued { violation, removed } => {
+                rescued += 1;
+                failures.entry(violation.reason).or_default().1 += 1;
+                let dropped = removed.iter().map(|i| format!("level {} ({})", i + 1, nums[*i])).collect::<Vec<_>>();
+                format!("Safe by removing {}, was {}", dropped.join(", "), describe(&violation))
+            }
+        };
+        out.push_str(&format!("{}: {}\n", levels, line));
+    }
+
+    out.push_str(&format!("\n{} reports: {} safe, {} rescued by the dampener, {} unsafe\n",
+        reports.len(), safe, rescued, reports.len() - safe - rescued));
+    for reason in Failure::ALL {
+        let (unsafe_count, rescued_count) = failures.get(&reason).copied().unwrap_or_default();
+        out.push_str(&format!("{:>22}: {} unsafe, {} rescued\n", reason.describe(), unsafe_count, rescued_count));
+    }
+    out
+}
+
+/// Monotone runs as their direction and length in levels, e.g. `+2 -3` for `1 3 2 1`.
+fn runs(nums: &[i32]) -> String {
+    sequence::monotone_runs(nums).iter()
+        .map(|run| format!("{}{}", run_symbol(run.slope), run.len))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn run_symbol(slope: Slope) -> char {
+    match slope {
+        Slope::Increasing => '+',
+        Slope::Decreasing => '-',
+        Slope::Flat => '=',
+    }
+}
+
+/// Why a pair of adjacent levels makes a report unsafe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Failure {
+    WrongDirection,
+    DirectionFlip,
+    ZeroStep,
+    StepTooSmall,
+    StepTooLarge,
+}
+
+impl Failure {
+    const ALL: [Failure; 5] = [Failure::WrongDirection, Failure::DirectionFlip, Failure::ZeroStep, Failure::StepTooSmall, Failure::StepTooLarge];
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Failure::WrongDirection => "direction not allowed",
+            Failure::DirectionFlip => "direction flip",
+            Failure::ZeroStep => "zero step",
+            Failure::StepTooSmall => "step too small",
+            Failure::StepTooLarge => "step too large",
+        }
+    }
+}
+
+/// The first offending pair of adjacent levels, `index` and `index + 1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Violation {
+    index: usize,
+    reason: Failure,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Verdict {
+    Safe,
+    Unsafe(Violation),
+    /// Unsafe as reported, but safe once the dampener drops the `removed` levels.
+    Rescued { violation: Violation, removed: Vec<usize> },
+}
+
+impl Verdict {
+    fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+}
+
+fn check_record(nums: &[i32], rule: &SafetyRule) -> Verdict {
+    let mut trend: Option<Slope> = None;
+    for (index, pair) in nums.windows(2).enumerate() {
+        let (prev, num) = (pair[0], pair[1]);
+        let unsafe_at = |reason| Verdict::Unsafe(Violation { index, reason });
+
+        let direction = Slope::of(prev, num);
+        if direction == Slope::Flat {
+            return unsafe_at(Failure::ZeroStep);
+        }
+
+        match trend {
+            None if !rule.trend.slopes().contains(&direction) => return unsafe_at(Failure::WrongDirection),
+            None => trend = Some(direction),
+            Some(d) if d != direction => return unsafe_at(Failure::DirectionFlip),
+            Some(_) => {}
+        }
+
+        let step = num.abs_diff(prev);
+        if step > rule.max_step.unsigned_abs() {
+            return unsafe_at(Failure::StepTooLarge);
+        }
+        if step < rule.min_step.unsigned_abs() {
+            return unsafe_at(Failure::StepTooSmall);
+        }
+    }
+
+    Verdict::Safe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The puzzle text's example reports and whether they are safe without and with the
+    /// dampener.
+    const EXAMPLES: [(&str, bool, bool); 6] = [
+        ("7 6 4 2 1", true, true),
+        ("1 2 7 8 9", false, false),
+        ("9 7 6 2 1", false, false),
+        ("1 3 2 4 5", false, true),
+        ("8 6 4 4 1", false, true),
+        ("1 3 6 7 9", true, true),
+    ];
+
+    const PUZZLE: SafetyRule = SafetyRule { min_step: 1, max_step: 3, trend: Trend::Either, removable: 1 };
+
+    #[test]
+    fn puzzle_examples() {
+        for (line, part1, part2) in EXAMPLES {
+            let nums = levels(line);
+            assert_eq!(check_record(&nums, &PUZZLE).is_safe(), part1, "{}", line);
+            assert_eq!(PUZZLE.verdict(&nums).is_safe(), part2, "{}", line);
+        }
+    }
+
+    fn levels(line: &str) -> Vec<i32> {
+        line.split_whitespace().map(|x| x.parse::<i32>().unwrap()).collect()
+    }
+
+    fn violation(index: usize, reason: Failure) -> Violation {
+        Violation { index, reason }
+    }
+
+    #[test]
+    fn puzzle_example_verdicts() {
+        let verdicts = EXAMPLES.map(|(line, _, _)| PUZZLE.verdict(&levels(line)));
+        assert_eq!(verdicts, [
+            Verdict::Safe,
+            Verdict::Unsafe(violation(1, Failure::StepTooLarge)),
+            Verdict::Unsafe(violation(2, Failure::StepTooLarge)),
+            Verdict::Rescued { violation: violation(1, Failure::DirectionFlip), removed: vec![2] },
+            Verdict::Rescued { violation: violation(2, Failure::ZeroStep), removed: vec![3] },
+            Verdict::Safe,
+        ]);
+    }
+
+    #[test]
+    fn first_violation_of_each_kind() {
+        let increasing = SafetyRule { trend: Trend::Increasing, removable: 0, ..PUZZLE };
+        assert_eq!(check_record(&levels("5 4 6"), &increasing), Verdict::Unsafe(violation(0, Failure::WrongDirection)));
+        assert_eq!(check_record(&levels("1 2 4 3 3"), &increasing), Verdict::Unsafe(violation(2, Failure::DirectionFlip)));
+
+        let wide = SafetyRule { min_step: 2, ..PUZZLE };
+        assert_eq!(check_record(&levels("9 7 6 4"), &wide), Verdict::Unsafe(violation(1, Failure::StepTooSmall)));
+        assert_eq!(check_record(&levels("9 7 5 3"), &wide), Verdict::Safe);
+    }
+
+    #[test]
+    fn report_names_the_violation_and_the_dropped_levels() {
+        let reports = EXAMPLES.map(|(line, _, _)| levels(line));
+        let out = report(&reports, &PUZZLE);
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "1 2 7 8 9: Unsafe, 2 -> 7 at levels 2-3 (step too large); runs +5, needs 2 removals");
+        assert_eq!(lines[3], "1 3 2 4 5: Safe by removing level 3 (2), was 3 -> 2 at levels 2-3 (direction flip)");
+        assert_eq!(lines[4], "8 6 4 4 1: Safe by removing level 4 (4), was 4 -> 4 at levels 3-4 (zero step)");
+        assert!(out.contains("\n6 reports: 2 safe, 2 rescued by the dampener, 2 unsafe\n"));
+        assert!(out.contains("        step too large: 2 unsafe, 0 rescued\n"));
+    }
+
+    /// Tries every combination of up to `removable` levels.
+    fn dampen_brute_force(rule: &SafetyRule, nums: &[i32]) -> Option<Vec<usize>> {
+        for count in 0..=rule.removable.min(nums.len()) {
+            let mut removed = (0..count).collect::<Vec<usize>>();
+            loop {
+                let kept = nums.iter().enumerate().filter(|(idx, _)| !removed.contains(idx)).map(|(_, val)| *val).collect::<Vec<i32>>();
+                if check_record(&kept, rule).is_safe() {
+                    return Some(removed);
+                }
+                if !next_combination(&mut removed, nums.len()) {
+                    break;
+                }
+            }
+        }
+        None
+    }
+
+    /// Advances `combination` to the next ascending selection out of `0..n`.
+    fn next_combination(combination: &mut [usize], n: usize) -> bool {
+        let k = combination.len();
+        for pos in (0..k).rev() {
+            if combination[pos] < n - k + pos {
+                combination[pos] += 1;
+                for next in pos + 1..k {
+                    combination[next] = combination[next - 1] + 1;
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Small reports with levels close together, so every kind of step shows up.
+    fn generated_reports(count: usize) -> Vec<Vec<i32>> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        (0..count).map(|_| {
+            let len = 1 + next(10) as usize;
+            let mut level = next(20) as i32;
+            (0..len).map(|_| {
+                level += next(11) as i32 - 5;
+                level
+            }).collect()
+        }).collect()
+    }
+
+    #[test]
+    fn dampener_matches_brute_force() {
+        let trends = [Trend::Either, Trend::Increasing, Trend::Decreasing];
+        for (idx, nums) in generated_reports(3000).iter().enumerate() {
+            let rule = SafetyRule { min_step: (idx % 3) as i32, max_step: 1 + (idx % 4) as i32, trend: trends[idx % 3], removable: idx % 4 };
+
+            let fast = rule.dampen(nums);
+            let brute = dampen_brute_force(&rule, nums);
+            assert_eq!(fast.as_ref().map(Vec::len), brute.as_ref().map(Vec::len), "{:?}", nums);
+            if let Some(removed) = &fast {
+                let kept = nums.iter().enumerate().filter(|(i, _)| !removed.contains(i)).map(|(_, v)| *v).collect::<Vec<_>>();
+                assert!(check_record(&kept, &rule).is_safe(), "dropping {:?} from {:?}", removed, nums);
+            }
+
+            let unlimited = SafetyRule { removable: nums.len(), ..rule };
+            let brute = dampen_brute_force(&unlimited, nums).map_or(nums.len(), |r| r.len());
+            assert_eq!(rule.min_removals(nums), brute, "{:?}", nums);
+        }
+    }
+}
+
+/*
+--- Day 2: Red-Nosed Reports ---
+Fortunately, the first location The Historians want to search isn't a long walk from the Chief Historian's office.
+
+While the Red-Nosed Reindeer nuclear fusion/fission plant appears to contain no sign of the Chief Historian, the engineers there run up to you as soon as they see you. Apparently, they still talk about the time Rudolph was saved through molecular synthesis from a single electron.
+
+They're quick to add that - since you're already here - they'd really appreciate your help analyzing some unusual data from the Red-Nosed reactor. You turn to check if The Historians are waiting for you, but they seem to have already divided into groups that are currently searching every corner of the facility. You offer to help with the unusual data.
+
+The unusual data (your puzzle input) consists of many reports, one report per line. Each report is a list of numbers called levels that are separated by spaces. For example:
+
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+This example data contains six reports each containing five levels.
+
+The engineers are trying to figure out which reports are safe. The Red-Nosed reactor safety systems can only tolerate levels that are either gradually increasing or gradually decreasing. So, a report only counts as safe if both of the following are true:
+
+The levels are either all increasing or all decreasing.
+Any two adjacent levels differ by at least one and at most three.
+In the example above, the reports can be found safe or unsafe by checking those rules:
+
+7 6 4 2 1: Safe because the levels are all decreasing by 1 or 2.
+1 2 7 8 9: Unsafe because 2 7 is an increase of 5.
+9 7 6 2 1: Unsafe because 6 2 is a decrease of 4.
+1 3 2 4 5: Unsafe because 1 3 is increasing but 3 2 is decreasing.
+8 6 4 4 1: Unsafe because 4 4 is neither an increase or a decrease.
+1 3 6 7 9: Safe because the levels are all increasing by 1, 2, or 3.
+So, in this example, 2 reports are safe.
+
+Analyze the unusual data from the engineers. How many reports are safe?
+
+--- Part Two ---
+The engineers are surprised by the low number of safe reports until they realize they forgot to tell you about the Problem Dampener.
+
+The Problem Dampener is a reactor-mounted module that lets the reactor safety systems tolerate a single bad level in what would otherwise be a safe report. It's like the bad level never happened!
+
+Now, the same rules apply as before, except if removing a single level from an unsafe report would make it safe, the report instead counts as safe.
+
+More of the above example's reports are now safe:
+
+7 6 4 2 1: Safe without removing any level.
+1 2 7 8 9: Unsafe regardless of which level is removed.
+9 7 6 2 1: Unsafe regardless of which level is removed.
+1 3 2 4 5: Safe by removing the second level, 3.
+8 6 4 4 1: Safe by removing the third level, 4.
+1 3 6 7 9: Safe without removing any level.
+Thanks to the Problem Dampener, 4 reports are actually safe!
+
+Update your analysis by handling situations where the Problem Dampener can remove a single level from unsafe reports. How many reports are now safe?
+*/
\ No newline at end of file
@@ -0,0 +1,208 @@
+use crate::machine::State;
+use crate::Result;
+
+/// Accepted shape of one argument: a plain decimal number of `min_digits..=max_digits` digits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub min_digits: usize,
+    pub max_digits: usize,
+}
+
+impl ArgSpec {
+    pub const PUZZLE: ArgSpec = ArgSpec { min_digits: 1, max_digits: 3 };
+}
+
+/// Runs an instruction with its parsed arguments, one per `ArgSpec`.
+pub type Effect = fn(&mut State, &[u32]) -> Result<()>;
+
+/// How an instruction is written, `name(arg,arg,...)`, and what it does to the machine.
+#[derive(Clone, Debug)]
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub args: Vec<ArgSpec>,
+    pub effect: Effect,
+}
+
+impl InstructionSpec {
+    pub fn new(name: &'static str, arity: usize, effect: Effect) -> InstructionSpec {
+        InstructionSpec { name, args: vec![ArgSpec::PUZZLE; arity], effect }
+    }
+}
+
+/// The instructions the lexer recognises, tried in registration order.
+#[derive(Clone, Debug, Default)]
+pub struct InstructionSet {
+    specs: Vec<InstructionSpec>,
+}
+
+impl InstructionSet {
+    /// `mul(X,Y)`, `do()` and `don't()` from the puzzle.
+    pub fn builtin() -> InstructionSet {
+        InstructionSet {
+            specs: vec![
+                InstructionSpec::new("mul", 2, mul),
+                InstructionSpec::new("do", 0, enable),
+                InstructionSpec::new("don't", 0, disable),
+            ],
+        }
+    }
+
+    pub fn register(&mut self, spec: InstructionSpec) -> Result<()> {
+        if spec.name.is_empty() || spec.name.contains(['(', ',', ')']) {
+            return Err(format!("Invalid instruction name '{}'", spec.name).into());
+        }
+        if self.specs.iter().any(|s| s.name == spec.name) {
+            return Err(format!("Instruction '{}' is already registered", spec.name).into());
+        }
+        // Nine digits always fit in a u32
+        if let Some(arg) = spec.args.iter().find(|a| a.min_digits == 0 || a.min_digits > a.max_digits || a.max_digits > 9) {
+            return Err(format!("{}: arguments need 1 to 9 digits, got {}-{}", spec.name, arg.min_digits, arg.max_digits).into());
+        }
+
+        self.specs.push(spec);
+        Ok(())
+    }
+
+    pub fn specs(&self) -> &[InstructionSpec] {
+        &self.specs
+    }
+
+    pub fn spec(&self, op: usize) -> &InstructionSpec {
+        &self.specs[op]
+    }
+}
+
+/// Instructions beyond the puzzle that can be switched on with `--instruction <name>`.
+pub const EXTRA_NAMES: [&str; 2] = ["add", "sub"];
+
+pub fn extra(name: &str) -> Option<InstructionSpec> {
+    match name {
+        "add" => Some(InstructionSpec::new("add", 2, add)),
+        "sub" => Some(InstructionSpec::new("sub", 2, sub)),
+        _ => None,
+    }
+}
+
+fn mul(state: &mut State, args: &[u32]) -> Result<()> {
+    let product = (args[0] as i64).checked_mul(args[1] as i64)
+        .ok_or_else(|| format!("mul({},{}) overflows i64", args[0], args[1]))?;
+    state.accumulate(product)
+}
+
+fn enable(state: &mut State, _args: &[u32]) -> Result<()> {
+    state.enabled = true;
+    Ok(())
+}
+
+fn disable(state: &mut State, _args: &[u32]) -> Result<()> {
+    state.enabled = false;
+    Ok(())
+}
+
+fn add(state: &mut State, args: &[u32]) -> Result<()> {
+    state.accumulate(args[0] as i64 + args[1] as i64)
+}
+
+fn sub(state: &mut State, args: &[u32]) -> Result<()> {
+    state.accumulate(args[0] as i64 - args[1] as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::machine::{Config, Machine};
+
+    fn names(set: &InstructionSet, memory: &str) -> Vec<(&'static str, Vec<u32>)> {
+        tokenize(memory, set).into_iter()
+            .map(|t| (set.spec(t.instruction.op).name, t.instruction.args))
+            .collect()
+    }
+
+    #[test]
+    fn registered_instructions_are_found_after_the_builtins() {
+        let mut set = InstructionSet::builtin();
+        set.register(InstructionSpec::new("add", 2, add)).unwrap();
+        set.register(InstructionSpec::new("nop", 0, enable)).unwrap();
+        assert_eq!(set.specs().len(), 5);
+        assert_eq!(
+            names(&set, "add(1,2)nop()mul(3,4)add(5)"),
+            vec![("add", vec![1, 2]), ("nop", vec![]), ("mul", vec![3, 4])],
+        );
+    }
+
+    #[test]
+    fn extra_instructions_run_on_the_machine() {
+        let mut set = InstructionSet::builtin();
+        for name in EXTRA_NAMES {
+            set.register(extra(name).unwrap()).unwrap();
+        }
+        let tokens = tokenize("add(5,3)sub(2,10)don't()add(100,1)sub(1,2)mul(2,2)do()sub(7,1)mul(3,3)", &set);
+        assert_eq!(tokens.len(), 9);
+
+        // Part 1 ignores the conditionals: 8 - 8 + 101 - 1 + 4 + 6 + 9. Part 2 skips
+        // everything between don't() and do(): 8 - 8 + 6 + 9
+        assert_eq!(Machine::new(&set, Config::PART1).run(&tokens).unwrap(), 119);
+        assert_eq!(Machine::new(&set, Config::PART2).run(&tokens).unwrap(), 15);
+    }
+
+    #[test]
+    fn extra_effects_change_the_sum_only_when_enabled() {
+        let mut state = State { enabled: true, sum: 0 };
+        add(&mut state, &[999, 1]).unwrap();
+        sub(&mut state, &[1, 999]).unwrap();
+        assert_eq!(state.sum, 1000 - 998);
+
+        state.enabled = false;
+        add(&mut state, &[5, 5]).unwrap();
+        sub(&mut state, &[0, 5]).unwrap();
+        assert_eq!(state.sum, 2);
+        assert!(extra("div").is_none());
+    }
+
+    #[test]
+    fn custom_digit_limits_are_respected() {
+        let mut set = InstructionSet::default();
+        let wide = ArgSpec { min_digits: 2, max_digits: 9 };
+        set.register(InstructionSpec { name: "put", args: vec![wide, ArgSpec::PUZZLE], effect: add }).unwrap();
+        assert_eq!(
+            names(&set, "put(1,2)put(12,3)put(123456789,999)put(1234567890,1)put(10,1000)"),
+            vec![("put", vec![12, 3]), ("put", vec![123456789, 999])],
+        );
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected() {
+        let mut set = InstructionSet::builtin();
+        let err = set.register(InstructionSpec::new("mul", 1, add)).unwrap_err();
+        assert_eq!(err.to_string(), "Instruction 'mul' is already registered");
+        assert_eq!(set.specs().len(), 3);
+    }
+
+    #[test]
+    fn digit_limits_outside_one_to_nine_are_rejected() {
+        let mut set = InstructionSet::default();
+        for (min_digits, max_digits) in [(1, 10), (0, 3), (4, 3)] {
+            let spec = InstructionSpec { name: "big", args: vec![ArgSpec { min_digits, max_digits }], effect: add };
+            let err = set.register(spec).unwrap_err();
+            assert_eq!(err.to_string(), format!("big: arguments need 1 to 9 digits, got {min_digits}-{max_digits}"));
+        }
+        assert!(set.specs().is_empty());
+    }
+
+    #[test]
+    fn names_that_break_the_call_syntax_are_rejected() {
+        let mut set = InstructionSet::default();
+        for name in ["", "a(b", "a,b", "a)"] {
+            assert!(set.register(InstructionSpec::new(name, 0, enable)).is_err());
+        }
+    }
+
+    #[test]
+    fn mul_of_largest_arguments_overflows_i64() {
+        let mut state = State { enabled: true, sum: 0 };
+        mul(&mut state, &[u32::MAX, 2]).unwrap();
+        assert_eq!(state.sum, 2 * u32::MAX as i64);
+        assert!(mul(&mut state, &[u32::MAX, u32::MAX]).is_err());
+    }
+}
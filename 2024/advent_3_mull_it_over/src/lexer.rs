@@ -0,0 +1,226 @@
+use std::ops::Range;
+
+use crate::instructions::{InstructionSet, InstructionSpec};
+
+/// A call of the instruction registered at index `op` of the instruction set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: usize,
+    pub args: Vec<u32>,
+}
+
+/// An instruction found in the corrupted memory and the bytes it was read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub instruction: Instruction,
+    pub span: Range<usize>,
+}
+
+/// Scans the memory left to right. Anything that is not an exact `name(arg,...)` call of a
+/// registered instruction is skipped one byte at a time.
+pub fn tokenize(memory: &str, set: &InstructionSet) -> Vec<Token> {
+    let mut scanner = Scanner::new(set);
+    let mut tokens = scanner.feed(memory.as_bytes());
+    tokens.extend(scanner.finish());
+    tokens
+}
+
+/// Tokenizes memory that arrives in chunks. Bytes that could still be the start of an
+/// instruction are kept until the next chunk decides it, so a call split across two chunks is
+/// found just as if the memory had been read in one go.
+pub struct Scanner<'a> {
+    set: &'a InstructionSet,
+    pending: Vec<u8>,
+    /// Offset of `pending[0]` in the whole memory
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(set: &'a InstructionSet) -> Scanner<'a> {
+        Scanner { set, pending: Vec::new(), offset: 0 }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Token> {
+        self.pending.extend_from_slice(chunk);
+        self.scan(false)
+    }
+
+    /// Tokens left at the end of the memory, where an unfinished call can no longer complete.
+    pub fn finish(mut self) -> Vec<Token> {
+        self.scan(true)
+    }
+
+    fn scan(&mut self, at_end: bool) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut pos = 0;
+
+        while pos < self.pending.len() {
+            match instruction_at(&self.pending[pos..], self.set, at_end) {
+                Scan::Match(instruction, len) => {
+                    let start = self.offset + pos;
+                    tokens.push(Token { instruction, span: start..start + len });
+                    pos += len;
+                }
+                Scan::NoMatch => pos += 1,
+                Scan::Incomplete => break,
+            }
+        }
+
+        self.pending.drain(..pos);
+        self.offset += pos;
+        tokens
+    }
+}
+
+enum Scan {
+    Match(Instruction, usize),
+    NoMatch,
+    /// The bytes so far are a prefix of a call, the next chunk decides.
+    Incomplete,
+}
+
+/// The first registered instruction written at the start of `bytes`, and its length. Earlier
+/// registrations win, so an unfinished earlier one holds up a complete later one.
+fn instruction_at(bytes: &[u8], set: &InstructionSet, at_end: bool) -> Scan {
+    for (op, spec) in set.specs().iter().enumerate() {
+        match call_at(bytes, op, spec, at_end) {
+            Scan::NoMatch => continue,
+            scan => return scan,
+        }
+    }
+    Scan::NoMatch
+}
+
+fn call_at(bytes: &[u8], op: usize, spec: &InstructionSpec, at_end: bool) -> Scan {
+    // Running out of bytes before the call is decided only means "wait" mid-stream
+    let out_of_bytes = || if at_end { Scan::NoMatch } else { Scan::Incomplete };
+
+    let mut pos = 0;
+    let expect = |literal: &[u8], pos: &mut usize| -> Option<Scan> {
+        let available = &bytes[*pos..];
+        let n = literal.len().min(available.len());
+        if available[..n] != literal[..n] {
+            return Some(Scan::NoMatch);
+        }
+        if n < literal.len() {
+            return Some(out_of_bytes());
+        }
+        *pos += literal.len();
+        None
+    };
+
+    if let Some(scan) = expect(spec.name.as_bytes(), &mut pos).or_else(|| expect(b"(", &mut pos)) {
+        return scan;
+    }
+
+    let mut args = Vec::with_capacity(spec.args.len());
+    for (idx, arg) in spec.args.iter().enumerate() {
+        if idx > 0 {
+            if let Some(scan) = expect(b",", &mut pos) {
+                return scan;
+            }
+        }
+
+        let len = bytes[pos..].iter().take(arg.max_digits).take_while(|c| c.is_ascii_digit()).count();
+        if len < arg.max_digits && pos + len == bytes.len() {
+            // More digits may follow in the next chunk
+            return out_of_bytes();
+        }
+        if len < arg.min_digits {
+            return Scan::NoMatch;
+        }
+        args.push(bytes[pos..pos + len].iter().fold(0, |acc, c| acc * 10 + (c - b'0') as u32));
+        pos += len;
+    }
+
+    if let Some(scan) = expect(b")", &mut pos) {
+        return scan;
+    }
+    Scan::Match(Instruction { op, args }, pos)
+}
+
+/// Same tokens as `tokenize`, found with one regex alternative per registered instruction.
+#[cfg(feature = "regex")]
+pub fn tokenize_regex(memory: &str, set: &InstructionSet) -> crate::Result<Vec<Token>> {
+    let alternatives = set.specs().iter().map(|spec| {
+        let args = spec.args.iter().map(|a| format!(r"(\d{{{},{}}})", a.min_digits, a.max_digits)).collect::<Vec<_>>();
+        format!(r"({}\({}\))", regex::escape(spec.name), args.join(","))
+    }).collect::<Vec<_>>();
+    let regex = regex::Regex::new(&alternatives.join("|"))?;
+
+    let mut tokens = Vec::new();
+    for cap in regex.captures_iter(memory) {
+        // Each alternative is one group for the whole call followed by one per argument
+        let mut group = 1;
+        for (op, spec) in set.specs().iter().enumerate() {
+            if let Some(matched) = cap.get(group) {
+                let args = (0..spec.args.len())
+                    .map(|a| cap.get(group + 1 + a).ok_or("Regex match without an argument")?.as_str().parse::<u32>().map_err(Into::into))
+                    .collect::<crate::Result<Vec<_>>>()?;
+                tokens.push(Token { instruction: Instruction { op, args }, span: matched.range() });
+                break;
+            }
+            group += 1 + spec.args.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: [&str; 5] = [
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        "mul(1234,5)mul(123,4)mul(4*mul(6,9!?(12,34)mul ( 2 , 4 )mul(,1)mul(1,)mul(7,8",
+        "do(don't(do()don't()mumul(3,4)mul(mul(5,6)mmul(999,999)do()",
+        "mul(1,2)",
+    ];
+
+    /// Feeds `input` to a scanner in the given chunks and collects every token.
+    fn scan<'a>(set: &InstructionSet, chunks: impl Iterator<Item = &'a [u8]>) -> Vec<Token> {
+        let mut scanner = Scanner::new(set);
+        let mut tokens = chunks.flat_map(|chunk| scanner.feed(chunk)).collect::<Vec<_>>();
+        tokens.extend(scanner.finish());
+        tokens
+    }
+
+    fn calls(set: &InstructionSet, memory: &str) -> Vec<String> {
+        tokenize(memory, set).iter()
+            .map(|t| format!("{}{:?}@{}", set.spec(t.instruction.op).name, t.instruction.args, t.span.start))
+            .collect()
+    }
+
+    #[test]
+    fn near_misses_are_skipped() {
+        let set = InstructionSet::builtin();
+        assert_eq!(calls(&set, INPUTS[0]), ["mul[2, 4]@1", "mul[5, 5]@29", "mul[11, 8]@53", "mul[8, 5]@62"]);
+        assert_eq!(calls(&set, INPUTS[2]), ["mul[123, 4]@11"]);
+        assert_eq!(calls(&set, INPUTS[3]), ["do[]@9", "don't[]@13", "mul[3, 4]@22", "mul[5, 6]@34", "mul[999, 999]@43", "do[]@55"]);
+    }
+
+    #[test]
+    fn every_two_chunk_split_gives_the_same_tokens() {
+        let set = InstructionSet::builtin();
+        for input in INPUTS {
+            let bytes = input.as_bytes();
+            let tokens = tokenize(input, &set);
+            for split in 0..=bytes.len() {
+                let (head, tail) = bytes.split_at(split);
+                assert_eq!(scan(&set, [head, tail].into_iter()), tokens, "{input} split at {split}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_chunk_size_gives_the_same_tokens() {
+        let set = InstructionSet::builtin();
+        let all = INPUTS.concat();
+        let tokens = tokenize(&all, &set);
+        for size in 1..=all.len() {
+            assert_eq!(scan(&set, all.as_bytes().chunks(size)), tokens, "chunks of {size} bytes");
+        }
+    }
+}
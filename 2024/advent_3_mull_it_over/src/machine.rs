@@ -0,0 +1,78 @@
+use crate::instructions::InstructionSet;
+use crate::lexer::{Instruction, Token};
+use crate::Result;
+
+/// Which instructions the machine obeys. Part 1 ignores the conditionals, part 2 follows them.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub ignored: &'static [&'static str],
+}
+
+impl Config {
+    pub const PART1: Config = Config { ignored: &["do", "don't"] };
+    pub const PART2: Config = Config { ignored: &[] };
+}
+
+/// What instruction effects can see and change.
+#[derive(Clone, Copy, Debug)]
+pub struct State {
+    pub enabled: bool,
+    pub sum: i64,
+}
+
+impl State {
+    /// Adds `value` to the sum, unless a conditional has disabled the machine.
+    pub fn accumulate(&mut self, value: i64) -> Result<()> {
+        if self.enabled {
+            self.sum = self.sum.checked_add(value).ok_or("Sum of instruction results overflows i64")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Machine<'a> {
+    set: &'a InstructionSet,
+    config: Config,
+    pub state: State,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(set: &'a InstructionSet, config: Config) -> Machine<'a> {
+        Machine { set, config, state: State { enabled: true, sum: 0 } }
+    }
+
+    pub fn execute(&mut self, instruction: &Instruction) -> Result<()> {
+        let spec = self.set.spec(instruction.op);
+        if self.config.ignored.contains(&spec.name) {
+            return Ok(());
+        }
+        (spec.effect)(&mut self.state, &instruction.args)
+    }
+
+    pub fn run(mut self, tokens: &[Token]) -> Result<i64> {
+        for token in tokens {
+            self.execute(&token.instruction)?;
+        }
+        Ok(self.state.sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulating_past_i64_is_an_error() {
+        let mut state = State { enabled: true, sum: i64::MAX - 5 };
+        state.accumulate(5).unwrap();
+        assert_eq!(state.sum, i64::MAX);
+        assert!(state.accumulate(1).is_err());
+    }
+
+    #[test]
+    fn disabled_machine_ignores_overflowing_values() {
+        let mut state = State { enabled: false, sum: i64::MAX };
+        state.accumulate(1).unwrap();
+        assert_eq!(state.sum, i64::MAX);
+    }
+}
@@ -0,0 +1,160 @@
+use std::{fs::{self, File}, io::Read};
+
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Param, Params};
+use instructions::InstructionSet;
+use lexer::{Scanner, Token};
+use machine::{Config, Machine};
+
+mod instructions;
+mod lexer;
+mod machine;
+mod trace;
+
+const PARAMS: [Param; 1] = [
+    Param::new("chunk_size", "65536", Check::Positive),
+];
+
+fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+    let tracing = aoc_core::params::has_flag("--trace");
+    let disassembly = aoc_core::params::value("--disassemble");
+    // The page goes to stdout on its own, so it can be redirected straight into a file
+    let exporting = disassembly.as_deref() == Some("html");
+    if !exporting {
+        params.print();
+    }
+    let mut set = InstructionSet::builtin();
+
+    for name in aoc_core::params::values("--instruction") {
+        let spec = instructions::extra(&name)
+            .ok_or_else(|| format!("Unknown instruction '{}', expected one of: {}", name, instructions::EXTRA_NAMES.join(", ")))?;
+        set.register(spec)?;
+    }
+
+    let (part1, part2) = match aoc_core::params::has_flag("--regex") {
+        true => run(&regex_tokens(&fs::read_to_string("input.txt")?, &set)?, &set)?,
+        false => stream(File::open("input.txt")?, &set, params.get("chunk_size")?)?,
+    };
+    if !exporting {
+        println!("Part 1 answer: {}", part1);
+        println!("Part 2 answer: {}", part2);
+    }
+
+    if !tracing && disassembly.is_none() {
+        return Ok(());
+    }
+
+    let input = fs::read_to_string("input.txt")?;
+    let tokens = lexer::tokenize(&input, &set);
+
+    if tracing {
+        print!("{}", trace::trace(&input, &trace::steps(&tokens, &set)?));
+    }
+
+    // `--disassemble ansi` colours the memory in the terminal, `--disassemble html` prints a page
+    if let Some(format) = disassembly {
+        let steps = trace::steps(&tokens, &set)?;
+        match format.as_str() {
+            "ansi" => println!("{}", trace::disassemble_ansi(input.trim_end(), &steps, &set)),
+            "html" => print!("{}", trace::disassemble_html(&input, &steps, &set)),
+            _ => return Err(format!("Unknown disassembly format '{}', expected ansi or html", format).into()),
+        }
+    }
+
+    return Ok(());
+}
+
+/// Both answers in one pass over `reader`, reading `chunk_size` bytes at a time.
+fn stream(mut reader: impl Read, set: &InstructionSet, chunk_size: usize) -> Result<(i64, i64)> {
+    let mut part1 = Machine::new(set, Config::PART1);
+    let mut part2 = Machine::new(set, Config::PART2);
+    let mut scanner = Scanner::new(set);
+    let mut chunk = vec![0; chunk_size];
+
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        for token in scanner.feed(&chunk[..read]) {
+            part1.execute(&token.instruction)?;
+            part2.execute(&token.instruction)?;
+        }
+    }
+    for token in scanner.finish() {
+        part1.execute(&token.instruction)?;
+        part2.execute(&token.instruction)?;
+    }
+
+    Ok((part1.state.sum, part2.state.sum))
+}
+
+/// Both answers from tokens that are already in memory.
+fn run(tokens: &[Token], set: &InstructionSet) -> Result<(i64, i64)> {
+    Ok((Machine::new(set, Config::PART1).run(tokens)?, Machine::new(set, Config::PART2).run(tokens)?))
+}
+
+#[cfg(feature = "regex")]
+fn regex_tokens(input: &str, set: &InstructionSet) -> Result<Vec<Token>> {
+    lexer::tokenize_regex(input, set)
+}
+
+#[cfg(not(feature = "regex"))]
+fn regex_tokens(_input: &str, _set: &InstructionSet) -> Result<Vec<Token>> {
+    Err("--regex needs the solver built with `--features regex`".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_matches_the_whole_memory_at_every_chunk_size() {
+        let set = InstructionSet::builtin();
+        let memory = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))mul(1234,5)mul(999,999)";
+        let expected = run(&lexer::tokenize(memory, &set), &set).unwrap();
+        assert_eq!(expected, (161 + 999 * 999, 48 + 999 * 999));
+        for size in 1..=memory.len() {
+            assert_eq!(stream(memory.as_bytes(), &set, size).unwrap(), expected, "chunks of {size} bytes");
+        }
+    }
+}
+
+/* 
+--- Day 3: Mull It Over ---
+"Our computers are having issues, so I have no idea if we have any Chief Historians in stock! You're welcome to check the warehouse, though," says the mildly flustered shopkeeper at the North Pole Toboggan Rental Shop. The Historians head out to take a look.
+
+The shopkeeper turns to you. "Any chance you can see why our computers are having issues again?"
+
+The computer appears to be trying to run a program, but its memory (your puzzle input) is corrupted. All of the instructions have been jumbled up!
+
+It seems like the goal of the program is just to multiply some numbers. It does that with instructions like mul(X,Y), where X and Y are each 1-3 digit numbers. For instance, mul(44,46) multiplies 44 by 46 to get a result of 2024. Similarly, mul(123,4) would multiply 123 by 4.
+
+However, because the program's memory has been corrupted, there are also many invalid characters that should be ignored, even if they look like part of a mul instruction. Sequences like mul(4*, mul(6,9!, ?(12,34), or mul ( 2 , 4 ) do nothing.
+
+For example, consider the following section of corrupted memory:
+
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+Only the four highlighted sections are real mul instructions. Adding up the result of each instruction produces 161 (2*4 + 5*5 + 11*8 + 8*5).
+
+Scan the corrupted memory for uncorrupted mul instructions. What do you get if you add up all of the results of the multiplications?
+
+--- Part Two ---
+As you scan through the corrupted memory, you notice that some of the conditional statements are also still intact. If you handle some of the uncorrupted conditional statements in the program, you might be able to get an even more accurate result.
+
+There are two new instructions you'll need to handle:
+
+The do() instruction enables future mul instructions.
+The don't() instruction disables future mul instructions.
+Only the most recent do() or don't() instruction applies. At the beginning of the program, mul instructions are enabled.
+
+For example:
+
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+This corrupted memory is similar to the example from before, but this time the mul(5,5) and mul(11,8) instructions are disabled because there is a don't() instruction before them. The other mul instructions function normally, including the one at the end that gets re-enabled by a do() instruction.
+
+This time, the sum of the results is 48 (2*4 + 8*5).
+
+Handle the new instructions; what do you get if you add up all of the results of just the enabled multiplications?
+*/
\ No newline at end of file
@@ -0,0 +1,217 @@
+use crate::instructions::InstructionSet;
+use crate::lexer::Token;
+use crate::machine::{Config, Machine};
+use crate::Result;
+
+/// One executed instruction: whether the part 2 machine was enabled when it ran and how
+/// much it added to each part's sum.
+pub struct Step<'a> {
+    pub token: &'a Token,
+    pub enabled: bool,
+    pub part1: i64,
+    pub part2: i64,
+}
+
+/// Runs both parts side by side, recording every instruction.
+pub fn steps<'a>(tokens: &'a [Token], set: &InstructionSet) -> Result<Vec<Step<'a>>> {
+    let mut part1 = Machine::new(set, Config::PART1);
+    let mut part2 = Machine::new(set, Config::PART2);
+
+    let mut steps = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let (before1, before2, enabled) = (part1.state.sum, part2.state.sum, part2.state.enabled);
+        part1.execute(&token.instruction)?;
+        part2.execute(&token.instruction)?;
+        steps.push(Step { token, enabled, part1: part1.state.sum - before1, part2: part2.state.sum - before2 });
+    }
+    Ok(steps)
+}
+
+/// 1-based line and column of a byte offset, counting columns in characters.
+fn line_col(memory: &str, line_starts: &[usize], offset: usize) -> (usize, usize) {
+    let line = line_starts.partition_point(|start| *start <= offset);
+    let start = line_starts[line - 1];
+    (line, memory[start..offset].chars().count() + 1)
+}
+
+/// Table of every recognised instruction with its position, the enabled state and its
+/// contribution to both sums.
+pub fn trace(memory: &str, steps: &[Step]) -> String {
+    let line_starts = std::iter::once(0)
+        .chain(memory.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect::<Vec<_>>();
+
+    let mut out = format!("{:>8} {:>10} {:>8}  {:<20} {:>8} {:>8}\n", "offset", "line:col", "state", "instruction", "part 1", "part 2");
+    for step in steps {
+        let span = &step.token.span;
+        let (line, col) = line_col(memory, &line_starts, span.start);
+        out.push_str(&format!("{:>8} {:>10} {:>8}  {:<20} {:>8} {:>8}\n",
+            span.start,
+            format!("{}:{}", line, col),
+            if step.enabled { "enabled" } else { "disabled" },
+            &memory[span.clone()],
+            step.part1,
+            step.part2));
+    }
+    out
+}
+
+/// How a stretch of the memory is shown in the disassembly.
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Corrupted,
+    Counted,
+    Disabled,
+    Conditional,
+}
+
+impl Class {
+    fn of(step: &Step, set: &InstructionSet) -> Class {
+        let name = set.spec(step.token.instruction.op).name;
+        if Config::PART1.ignored.contains(&name) {
+            Class::Conditional
+        } else if step.enabled {
+            Class::Counted
+        } else {
+            Class::Disabled
+        }
+    }
+
+    fn ansi(&self) -> &'static str {
+        match self {
+            Class::Corrupted => "\x1b[2m",
+            Class::Counted => "\x1b[1;32m",
+            Class::Disabled => "\x1b[31m",
+            Class::Conditional => "\x1b[1;33m",
+        }
+    }
+
+    fn css(&self) -> &'static str {
+        match self {
+            Class::Corrupted => "corrupted",
+            Class::Counted => "counted",
+            Class::Disabled => "disabled",
+            Class::Conditional => "conditional",
+        }
+    }
+}
+
+/// The memory split into corrupted stretches and instructions.
+fn segments<'a>(memory: &'a str, steps: &[Step], set: &InstructionSet) -> Vec<(Class, &'a str)> {
+    let mut segments = Vec::new();
+    let mut pos = 0;
+    for step in steps {
+        let span = &step.token.span;
+        if span.start > pos {
+            segments.push((Class::Corrupted, &memory[pos..span.start]));
+        }
+        segments.push((Class::of(step, set), &memory[span.clone()]));
+        pos = span.end;
+    }
+    if pos < memory.len() {
+        segments.push((Class::Corrupted, &memory[pos..]));
+    }
+    segments
+}
+
+/// The memory with counted instructions in green, disabled ones in red, conditionals in
+/// yellow and the corrupted rest dimmed.
+pub fn disassemble_ansi(memory: &str, steps: &[Step], set: &InstructionSet) -> String {
+    segments(memory, steps, set).into_iter()
+        .map(|(class, text)| format!("{}{}\x1b[0m", class.ansi(), text))
+        .collect()
+}
+
+/// A standalone HTML page of the same colouring.
+pub fn disassemble_html(memory: &str, steps: &[Step], set: &InstructionSet) -> String {
+    let mut out = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Day 3 disassembly</title>\n<style>\n",
+        "pre { white-space: pre-wrap; word-break: break-all; }\n",
+        ".corrupted { color: #999; }\n",
+        ".counted { color: #080; font-weight: bold; }\n",
+        ".disabled { color: #c00; }\n",
+        ".conditional { color: #b80; font-weight: bold; }\n",
+        "</style>\n</head>\n<body>\n<pre>"));
+
+    for (class, text) in segments(memory, steps, set) {
+        let escaped = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        out.push_str(&format!("<span class=\"{}\">{}</span>", class.css(), escaped));
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    /// Three lines, with a two byte character before the last instructions and markup to escape.
+    const MEMORY: &str = "xmul(2,4)\ndon't()mul(5,5)\n<é&do()>mul(11,8)";
+
+    fn with_steps<T>(f: impl FnOnce(&[Step], &InstructionSet) -> T) -> T {
+        let set = InstructionSet::builtin();
+        let tokens = tokenize(MEMORY, &set);
+        let steps = steps(&tokens, &set).unwrap();
+        f(&steps, &set)
+    }
+
+    #[test]
+    fn steps_follow_the_conditionals() {
+        with_steps(|steps, _| {
+            let summary = steps.iter().map(|s| (s.token.span.start, s.enabled, s.part1, s.part2)).collect::<Vec<_>>();
+            assert_eq!(summary, [(1, true, 8, 8), (10, true, 0, 0), (17, false, 25, 0), (30, false, 0, 0), (35, true, 88, 88)]);
+        });
+    }
+
+    #[test]
+    fn trace_gives_offsets_and_line_columns() {
+        let table = with_steps(|steps, _| trace(MEMORY, steps));
+        let rows = table.lines().map(|line| line.split_whitespace().collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(rows, [
+            vec!["offset", "line:col", "state", "instruction", "part", "1", "part", "2"],
+            vec!["1", "1:2", "enabled", "mul(2,4)", "8", "8"],
+            vec!["10", "2:1", "enabled", "don't()", "0", "0"],
+            vec!["17", "2:8", "disabled", "mul(5,5)", "25", "0"],
+            vec!["30", "3:4", "disabled", "do()", "0", "0"],
+            vec!["35", "3:9", "enabled", "mul(11,8)", "88", "88"],
+        ]);
+    }
+
+    #[test]
+    fn line_col_counts_characters_from_the_line_start() {
+        let line_starts = [0, 10, 26];
+        assert_eq!(line_col(MEMORY, &line_starts, 0), (1, 1));
+        assert_eq!(line_col(MEMORY, &line_starts, 9), (1, 10));
+        assert_eq!(line_col(MEMORY, &line_starts, 10), (2, 1));
+        assert_eq!(line_col(MEMORY, &line_starts, 26), (3, 1));
+        assert_eq!(line_col(MEMORY, &line_starts, 29), (3, 3));
+    }
+
+    #[test]
+    fn segments_cover_the_whole_memory() {
+        let segments = with_steps(|steps, set| segments(MEMORY, steps, set));
+        let named = segments.iter().map(|(class, text)| (class.css(), *text)).collect::<Vec<_>>();
+        assert_eq!(named, [
+            ("corrupted", "x"),
+            ("counted", "mul(2,4)"),
+            ("corrupted", "\n"),
+            ("conditional", "don't()"),
+            ("disabled", "mul(5,5)"),
+            ("corrupted", "\n<é&"),
+            ("conditional", "do()"),
+            ("corrupted", ">"),
+            ("counted", "mul(11,8)"),
+        ]);
+        assert_eq!(named.iter().map(|(_, text)| *text).collect::<String>(), MEMORY);
+    }
+
+    #[test]
+    fn html_escapes_the_memory() {
+        let page = with_steps(|steps, set| disassemble_html(MEMORY, steps, set));
+        assert!(page.contains("<span class=\"corrupted\">\n&lt;é&amp;</span><span class=\"conditional\">do()</span><span class=\"corrupted\">&gt;</span>"));
+        assert!(page.contains("<span class=\"disabled\">mul(5,5)</span>"));
+        assert!(!page.contains("<é"));
+    }
+}
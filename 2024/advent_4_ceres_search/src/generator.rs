@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use aoc_core::Grid;
+
+use crate::search::{Automaton, Heading};
+use crate::Result;
+
+const HEADINGS: [Heading; 8] = [Heading::N, Heading::NE, Heading::E, Heading::SE, Heading::S, Heading::SW, Heading::W, Heading::NW];
+const ATTEMPTS: usize = 10_000;
+
+/// Linear congruential generator, enough to scatter words reproducibly from a seed.
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+}
+
+/// A generated word search and how often each distinct word occurs in it.
+pub struct Puzzle {
+    pub grid: Grid,
+    pub counts: HashMap<String, usize>,
+}
+
+/// Builds a `rows` x `cols` word search holding one copy of every entry of `words` (repeat a
+/// word for more copies) in random directions, then fills the rest with letters of the words.
+///
+/// The counts are what the word search finds with the filler left blank, so they include
+/// words hidden inside others, e.g. MAS inside XMAS. Filler that spells an extra occurrence
+/// is re-rolled until the filled grid has exactly those counts.
+pub fn generate(rows: i32, cols: i32, words: &[&str], seed: u64) -> Result<Puzzle> {
+    let mut rng = Lcg(seed);
+    let mut grid = Grid { rows, cols, ..Grid::default() };
+
+    for word in words {
+        place(&mut grid, word, &mut rng)?;
+    }
+
+    let mut distinct = words.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    let automaton = Automaton::new(&distinct);
+    let expected = automaton.search(&grid);
+
+    let mut alphabet = words.iter().flat_map(|w| w.chars()).collect::<Vec<_>>();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    if alphabet.is_empty() {
+        return Err("No letters to fill the grid with".into());
+    }
+
+    let filler = (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .filter(|pos| !grid.cells.contains_key(pos))
+        .collect::<Vec<_>>();
+    for pos in &filler {
+        grid.cells.insert(*pos, alphabet[rng.below(alphabet.len())]);
+    }
+
+    for _ in 0..ATTEMPTS {
+        let found = automaton.search(&grid);
+        let extra = found.iter().filter(|m| !expected.contains(m)).collect::<Vec<_>>();
+        if extra.is_empty() {
+            let mut counts = HashMap::new();
+            for m in &found {
+                *counts.entry(distinct[m.word].to_string()).or_insert(0) += 1;
+            }
+            return Ok(Puzzle { grid, counts });
+        }
+
+        // Every extra occurrence uses some filler, otherwise it would be expected
+        for m in extra {
+            let filled = m.cells.iter().filter(|pos| filler.contains(pos)).collect::<Vec<_>>();
+            let pos = filled[rng.below(filled.len())];
+            grid.cells.insert(*pos, alphabet[rng.below(alphabet.len())]);
+        }
+    }
+
+    Err(format!("Could not fill the grid without spelling extra words in {} attempts", ATTEMPTS).into())
+}
+
+/// Writes `word` at a random start and heading where it fits, sharing only matching letters.
+fn place(grid: &mut Grid, word: &str, rng: &mut Lcg) -> Result<()> {
+    let letters = word.chars().collect::<Vec<_>>();
+    for _ in 0..ATTEMPTS {
+        let start = (rng.below(grid.rows as usize) as i32, rng.below(grid.cols as usize) as i32);
+        let (d_row, d_col) = HEADINGS[rng.below(HEADINGS.len())].delta();
+        let cells = (0..letters.len() as i32).map(|i| (start.0 + d_row * i, start.1 + d_col * i)).collect::<Vec<_>>();
+
+        let fits = cells.iter().zip(&letters).all(|(pos, ch)| grid.in_bounds(*pos) && grid.get(*pos).is_none_or(|c| c == *ch));
+        // Landing entirely on letters already there would not add a copy
+        let adds_letters = cells.iter().any(|pos| grid.get(*pos).is_none());
+        if fits && adds_letters {
+            for (pos, ch) in cells.into_iter().zip(letters) {
+                grid.cells.insert(pos, ch);
+            }
+            return Ok(());
+        }
+    }
+
+    Err(format!("No room for {} in a {}x{} grid", word, grid.rows, grid.cols).into())
+}
@@ -0,0 +1,202 @@
+use std::fs;
+
+pub use aoc_core::{Error, Result};
+use aoc_core::{Check, Grid, Param, Params};
+use search::Automaton;
+use template::Template;
+
+mod generator;
+mod render;
+mod search;
+mod template;
+
+/// Size and seed of a word search made with `--generate`.
+const PARAMS: [Param; 3] = [
+    Param::new("rows", "10", Check::Positive),
+    Param::new("cols", "10", Check::Positive),
+    Param::new("seed", "2024", Check::NonNegative),
+];
+
+/// XMAS in a line, either straight or diagonal, in any direction.
+const PART1: [&str; 2] = ["XMAS", "X.../.M../..A./...S"];
+/// Two MAS crossing on their A.
+const PART2: [&str; 1] = ["M.S/.A./M.S"];
+
+fn main() -> Result<()> {
+    let params = Params::from_args(&PARAMS)?;
+
+    // Words to find in one pass with `--word <word>`
+    let words = aoc_core::params::values("--word");
+    let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+
+    if aoc_core::params::has_flag("--generate") {
+        params.print();
+        // Repeating a word places more copies of it
+        let placed = if words.is_empty() { vec!["XMAS"] } else { words };
+        let puzzle = generator::generate(params.get("rows")?, params.get("cols")?, &placed, params.get("seed")?)?;
+
+        print!("{}", puzzle.grid.render());
+        let mut counts = puzzle.counts.into_iter().collect::<Vec<_>>();
+        counts.sort_unstable();
+        for (word, count) in counts {
+            println!("{}: {}", word, count);
+        }
+        return Ok(());
+    }
+
+    let grid = fs::read_to_string("input.txt")?.parse::<Grid>()?;
+
+    // The page goes to stdout on its own, so it can be redirected straight into a file
+    let highlight = aoc_core::params::value("--highlight");
+    let exporting = highlight.as_deref() == Some("html");
+    if !exporting {
+        part1(&grid)?;
+        part2(&grid)?;
+        for shape in aoc_core::params::values("--shape") {
+            let template = shape.parse::<Template>()?;
+            println!("Shape {}: {}", shape, template::placements(&[template], &grid).len());
+        }
+    }
+
+    let mut searched: Vec<&str> = Vec::new();
+    for word in if words.is_empty() { &["XMAS"][..] } else { &words } {
+        if !searched.contains(word) {
+            searched.push(word);
+        }
+    }
+    let matches = Automaton::new(&searched).search(&grid);
+    if !words.is_empty() && !exporting {
+        for found in &matches {
+            println!("{} at {:?} heading {:?}: {:?}", searched[found.word], found.start, found.heading, found.cells);
+        }
+    }
+
+    // `--highlight ansi` colours the found words in the terminal, `--highlight html` prints a page
+    if let Some(format) = highlight {
+        match format.as_str() {
+            "ansi" => print!("{}", render::highlight_ansi(&grid, &matches)),
+            "html" => print!("{}", render::highlight_html(&grid, &matches, &searched)),
+            _ => return Err(format!("Unknown highlight format '{}', expected ansi or html", format).into()),
+        }
+    }
+
+    return Ok(());
+}
+
+fn parse_templates(shapes: &[&str]) -> Result<Vec<Template>> {
+    shapes.iter().map(|shape| shape.parse()).collect()
+}
+
+fn part1(grid: &Grid) -> Result<()> {
+    let result = template::placements(&parse_templates(&PART1)?, grid).len();
+
+    println!("Part 1 answer: {}", result);
+    return Ok(());
+}
+
+fn part2(grid: &Grid) -> Result<()> {
+    let result = template::placements(&parse_templates(&PART2)?, grid).len();
+
+    println!("Part 2 answer: {}", result);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX\n";
+
+    /// Part 1 again with the word search, which has to find the same placements as the templates.
+    fn assert_search_matches_templates(grid: &Grid) -> usize {
+        let mut by_template = template::placements(&parse_templates(&PART1).unwrap(), grid);
+        let mut by_search = Automaton::new(&["XMAS"]).search(grid).into_iter().map(|m| m.cells).collect::<Vec<_>>();
+        for placements in [&mut by_template, &mut by_search] {
+            for cells in placements.iter_mut() {
+                cells.sort_unstable();
+            }
+            placements.sort_unstable();
+        }
+        assert_eq!(by_template, by_search);
+        by_search.len()
+    }
+
+    #[test]
+    fn search_matches_templates_on_the_example() {
+        assert_eq!(assert_search_matches_templates(&EXAMPLE.parse().unwrap()), 18);
+    }
+
+    #[test]
+    fn search_matches_templates_on_generated_grids() {
+        for seed in 0..40 {
+            let puzzle = generator::generate(12, 12, &["XMAS"; 8], seed).unwrap();
+            assert_eq!(assert_search_matches_templates(&puzzle.grid), puzzle.counts["XMAS"], "seed {seed}");
+        }
+    }
+}
+
+/*
+--- Day 4: Ceres Search ---
+"Looks like the Chief's not here. Next!" One of The Historians pulls out a device and pushes the only button on it. After a brief flash, you recognize the interior of the Ceres monitoring station!
+
+As the search for the Chief continues, a small Elf who lives on the station tugs on your shirt; she'd like to know if you could help her with her word search (your puzzle input). She only has to find one word: XMAS.
+
+This word search allows words to be horizontal, vertical, diagonal, written backwards, or even overlapping other words. It's a little unusual, though, as you don't merely need to find one instance of XMAS - you need to find all of them. Here are a few ways XMAS might appear, where irrelevant characters have been replaced with .:
+
+
+..X...
+.SAMX.
+.A..A.
+XMAS.S
+.X....
+The actual word search will be full of letters instead. For example:
+
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+In this word search, XMAS occurs a total of 18 times; here's the same word search again, but where letters not involved in any XMAS have been replaced with .:
+
+....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX
+Take a look at the little Elf's word search. How many times does XMAS appear?
+
+--- Part Two ---
+The Elf looks quizzically at you. Did you misunderstand the assignment?
+
+Looking for the instructions, you flip over the word search to find that this isn't actually an XMAS puzzle; it's an X-MAS puzzle in which you're supposed to find two MAS in the shape of an X. One way to achieve that is like this:
+
+M.S
+.A.
+M.S
+Irrelevant characters have again been replaced with . in the above diagram. Within the X, each MAS can be written forwards or backwards.
+
+Here's the same example from before, but this time all of the X-MASes have been kept instead:
+
+.M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+..........
+In this example, an X-MAS appears 9 times.
+
+Flip the word search from the instructions back over to the word search side and try again. How many times does an X-MAS appear? */
\ No newline at end of file
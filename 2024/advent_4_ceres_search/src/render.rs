@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use aoc_core::Grid;
+
+use crate::search::Match;
+
+const ANSI_COLOURS: [&str; 6] = ["\x1b[1;32m", "\x1b[1;33m", "\x1b[1;36m", "\x1b[1;35m", "\x1b[1;34m", "\x1b[1;31m"];
+const CSS_COLOURS: [&str; 6] = ["#080", "#b80", "#088", "#a0a", "#00c", "#c00"];
+
+/// Colour index of every cell covered by a match, by the word it belongs to. Where words
+/// cross, the one found last wins.
+fn highlighted(matches: &[Match]) -> HashMap<(i32, i32), usize> {
+    let mut cells = HashMap::new();
+    for m in matches {
+        for pos in &m.cells {
+            cells.insert(*pos, m.word % ANSI_COLOURS.len());
+        }
+    }
+    cells
+}
+
+/// The grid with found words in colour, one colour per word, and every other letter dimmed.
+pub fn highlight_ansi(grid: &Grid, matches: &[Match]) -> String {
+    let cells = highlighted(matches);
+    let mut out = String::new();
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            let ch = grid.get((row, col)).unwrap_or(' ');
+            match cells.get(&(row, col)) {
+                Some(colour) => out.push_str(&format!("{}{}\x1b[0m", ANSI_COLOURS[*colour], ch)),
+                None => out.push_str(&format!("\x1b[2m{}\x1b[0m", ch)),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// A standalone HTML page of the same highlighting.
+pub fn highlight_html(grid: &Grid, matches: &[Match], words: &[&str]) -> String {
+    let cells = highlighted(matches);
+    let mut out = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Day 4 word search</title>\n<style>\n",
+        "pre { font-size: 1.2em; letter-spacing: 0.3em; }\n",
+        ".filler { color: #bbb; }\n",
+        "</style>\n</head>\n<body>\n"));
+
+    out.push_str("<p>");
+    for (idx, word) in words.iter().enumerate() {
+        let count = matches.iter().filter(|m| m.word == idx).count();
+        out.push_str(&format!("<b style=\"color: {}\">{}</b> &times; {} ", CSS_COLOURS[idx % CSS_COLOURS.len()], escape(word), count));
+    }
+    out.push_str("</p>\n<pre>");
+
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            let ch = escape(&grid.get((row, col)).unwrap_or(' ').to_string());
+            match cells.get(&(row, col)) {
+                Some(colour) => out.push_str(&format!("<b style=\"color: {}\">{}</b>", CSS_COLOURS[*colour], ch)),
+                None => out.push_str(&format!("<span class=\"filler\">{}</span>", ch)),
+            }
+        }
+        out.push('\n');
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Automaton;
+
+    #[test]
+    fn html_escapes_letters_and_words() {
+        let grid = "<&>\nA<B\n".parse::<Grid>().unwrap();
+        let matches = Automaton::new(&["<&"]).search(&grid);
+        let page = highlight_html(&grid, &matches, &["<&"]);
+        assert!(page.contains("&lt;&amp;</b> &times; 2 "));
+        assert!(page.contains("<b style=\"color: #080\">&lt;</b><b style=\"color: #080\">&amp;</b><span class=\"filler\">&gt;</span>"));
+        assert!(!page.contains("<&"));
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use aoc_core::Grid;
+
+/// The 8 ways a word can run through the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Heading {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Heading {
+    /// One heading of every line through the grid, the other 4 are these read backwards.
+    const FORWARD: [Heading; 4] = [Heading::E, Heading::S, Heading::SE, Heading::SW];
+
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Heading::N => (-1, 0),
+            Heading::NE => (-1, 1),
+            Heading::E => (0, 1),
+            Heading::SE => (1, 1),
+            Heading::S => (1, 0),
+            Heading::SW => (1, -1),
+            Heading::W => (0, -1),
+            Heading::NW => (-1, -1),
+        }
+    }
+
+    pub fn reverse(&self) -> Heading {
+        match self {
+            Heading::N => Heading::S,
+            Heading::NE => Heading::SW,
+            Heading::E => Heading::W,
+            Heading::SE => Heading::NW,
+            Heading::S => Heading::N,
+            Heading::SW => Heading::NE,
+            Heading::W => Heading::E,
+            Heading::NW => Heading::SE,
+        }
+    }
+}
+
+/// A word found in the grid, `cells` listed from its first letter to its last.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub word: usize,
+    pub start: (i32, i32),
+    pub heading: Heading,
+    pub cells: Vec<(i32, i32)>,
+}
+
+#[derive(Default)]
+struct Node {
+    next: HashMap<char, usize>,
+    fail: usize,
+    /// Words ending here, including those reached through the failure links.
+    outputs: Vec<usize>,
+}
+
+/// Aho-Corasick automaton over a dictionary, finding every word, overlapping ones included,
+/// in a single pass over a line of letters.
+pub struct Automaton {
+    nodes: Vec<Node>,
+    lengths: Vec<usize>,
+}
+
+impl Automaton {
+    pub fn new(words: &[&str]) -> Automaton {
+        let mut nodes = vec![Node::default()];
+        for (idx, word) in words.iter().enumerate() {
+            let mut node = 0;
+            for ch in word.chars() {
+                node = match nodes[node].next.get(&ch) {
+                    Some(next) => *next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            if !word.is_empty() {
+                nodes[node].outputs.push(idx);
+            }
+        }
+
+        // Breadth first, so a node's failure target is complete before the node itself
+        let mut queue = nodes[0].next.values().copied().collect::<VecDeque<_>>();
+        while let Some(node) = queue.pop_front() {
+            let edges = nodes[node].next.iter().map(|(ch, next)| (*ch, *next)).collect::<Vec<_>>();
+            for (ch, next) in edges {
+                let mut fail = nodes[node].fail;
+                let target = loop {
+                    if let Some(target) = nodes[fail].next.get(&ch) {
+                        break *target;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[next].fail = target;
+                let inherited = nodes[target].outputs.clone();
+                nodes[next].outputs.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        Automaton { nodes, lengths: words.iter().map(|w| w.chars().count()).collect() }
+    }
+
+    /// `(word, end index)` of every word occurring in `letters`.
+    fn scan(&self, letters: impl Iterator<Item = char>) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        let mut node = 0;
+        for (idx, ch) in letters.enumerate() {
+            node = loop {
+                if let Some(next) = self.nodes[node].next.get(&ch) {
+                    break *next;
+                }
+                if node == 0 {
+                    break 0;
+                }
+                node = self.nodes[node].fail;
+            };
+            found.extend(self.nodes[node].outputs.iter().map(|word| (*word, idx)));
+        }
+        found
+    }
+
+    /// Every occurrence of every word along the grid's rows, columns and diagonals, in both
+    /// directions. A placement is reported once per word, so palindromes and one letter
+    /// words aren't counted again when read backwards or along another line.
+    pub fn search(&self, grid: &Grid) -> Vec<Match> {
+        let mut seen = HashSet::new();
+        let mut matches = Vec::new();
+
+        for heading in Heading::FORWARD {
+            for line in lines(grid, heading) {
+                let backwards = line.iter().rev().copied().collect::<Vec<_>>();
+                for (cells, heading) in [(line, heading), (backwards, heading.reverse())] {
+                    let letters = cells.iter().map(|pos| grid.get(*pos).unwrap_or(' '));
+                    for (word, end) in self.scan(letters) {
+                        let cells = cells[end + 1 - self.lengths[word]..=end].to_vec();
+                        let mut key = cells.clone();
+                        key.sort_unstable();
+                        if seen.insert((word, key)) {
+                            matches.push(Match { word, start: cells[0], heading, cells });
+                        }
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// Cells of every full line through the grid going `heading`.
+fn lines(grid: &Grid, heading: Heading) -> Vec<Vec<(i32, i32)>> {
+    let (d_row, d_col) = heading.delta();
+    let mut lines = Vec::new();
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            // Lines start at the cells whose predecessor is off the grid
+            if grid.in_bounds((row - d_row, col - d_col)) {
+                continue;
+            }
+            let mut line = Vec::new();
+            let mut pos = (row, col);
+            while grid.in_bounds(pos) {
+                line.push(pos);
+                pos = (pos.0 + d_row, pos.1 + d_col);
+            }
+            lines.push(line);
+        }
+    }
+    lines
+}
@@ -0,0 +1,102 @@
+use std::{collections::HashSet, str::FromStr};
+
+use aoc_core::Grid;
+
+use crate::{Error, Result};
+
+type Symmetry = fn((i32, i32)) -> (i32, i32);
+
+/// The 8 rotations and reflections of a `(row, col)` offset.
+const SYMMETRIES: [Symmetry; 8] = [
+    |(r, c)| (r, c),
+    |(r, c)| (c, -r),
+    |(r, c)| (-r, -c),
+    |(r, c)| (-c, r),
+    |(r, c)| (r, -c),
+    |(r, c)| (-r, c),
+    |(r, c)| (c, r),
+    |(r, c)| (-c, -r),
+];
+
+/// A 2D shape of letters, rows separated by `/` and `.` matching anything, e.g. `M.S/.A./M.S`.
+/// Only the letters are kept, as offsets from the top left corner of their bounding box.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    cells: Vec<((i32, i32), char)>,
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut cells = Vec::new();
+        for (row, line) in s.split('/').enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch != '.' {
+                    cells.push(((row as i32, col as i32), ch));
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err(format!("Template '{}' has no letters", s).into());
+        }
+        Ok(Template::normalized(cells))
+    }
+}
+
+impl Template {
+    fn normalized(mut cells: Vec<((i32, i32), char)>) -> Template {
+        let min_row = cells.iter().map(|((r, _), _)| *r).min().unwrap_or(0);
+        let min_col = cells.iter().map(|((_, c), _)| *c).min().unwrap_or(0);
+        for ((r, c), _) in cells.iter_mut() {
+            *r -= min_row;
+            *c -= min_col;
+        }
+        cells.sort_unstable();
+        Template { cells }
+    }
+
+    /// The distinct shapes among the 8 rotations and reflections, so a symmetric template
+    /// isn't counted more than once at the same spot.
+    pub fn orientations(&self) -> Vec<Template> {
+        let mut orientations: Vec<Template> = Vec::new();
+        for symmetry in SYMMETRIES {
+            let oriented = Template::normalized(self.cells.iter().map(|(pos, ch)| (symmetry(*pos), *ch)).collect());
+            if !orientations.contains(&oriented) {
+                orientations.push(oriented);
+            }
+        }
+        orientations
+    }
+
+    /// Cells of every spot where the template, as it is, matches the grid.
+    fn matches(&self, grid: &Grid) -> Vec<Vec<(i32, i32)>> {
+        let mut found = Vec::new();
+        for row in 0..grid.rows {
+            for col in 0..grid.cols {
+                let cells = self.cells.iter().map(|((r, c), _)| (row + r, col + c)).collect::<Vec<_>>();
+                if cells.iter().zip(&self.cells).all(|(pos, (_, ch))| grid.get(*pos) == Some(*ch)) {
+                    found.push(cells);
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Every placement of any of the templates in any orientation, each set of cells once.
+pub fn placements(templates: &[Template], grid: &Grid) -> Vec<Vec<(i32, i32)>> {
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for template in templates {
+        for oriented in template.orientations() {
+            // Template cells are sorted, so equal placements have equal cell lists
+            for cells in oriented.matches(grid) {
+                if seen.insert(cells.clone()) {
+                    found.push(cells);
+                }
+            }
+        }
+    }
+    found
+}
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::RuleSet;
+
+/// Why a set of pages has no single valid order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unordered {
+    Cycle(Vec<u32>),
+    /// Neither page has to come before the other, directly or through other pages
+    Incomparable(u32, u32),
+}
+
+impl RuleSet {
+    /// Rules between pages of `pages`, without duplicates.
+    fn successors(&self, page: u32, pages: &BTreeSet<u32>) -> BTreeSet<u32> {
+        self.page_rules.get(&page).into_iter().flatten().copied().filter(|p| pages.contains(p)).collect()
+    }
+
+    /// Every page mentioned by a rule.
+    pub fn pages(&self) -> BTreeSet<u32> {
+        self.page_rules.iter().flat_map(|(before, afters)| std::iter::once(*before).chain(afters.iter().copied())).collect()
+    }
+
+    /// For every page, all pages the rules put after it, directly or through other pages.
+    pub fn closure(&self) -> BTreeMap<u32, BTreeSet<u32>> {
+        let pages = self.pages();
+        pages.iter().map(|page| {
+            let mut reached = BTreeSet::new();
+            let mut stack = vec![*page];
+            while let Some(current) = stack.pop() {
+                for next in self.successors(current, &pages) {
+                    if reached.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            (*page, reached)
+        }).collect()
+    }
+
+    /// Rules `X|Y` that other rules already imply, each with a path `X -> ... -> Y` avoiding
+    /// the rule itself. With cycles, rules can be redundant because of each other, so they
+    /// can't necessarily all be dropped together.
+    pub fn redundant_rules(&self) -> Vec<(u32, u32, Vec<u32>)> {
+        let pages = self.pages();
+        let mut redundant = Vec::new();
+        for before in &pages {
+            for after in self.successors(*before, &pages) {
+                let skip = (*before, after);
+                if let Some(path) = self.path(*before, after, &pages, Some(skip)) {
+                    redundant.push((*before, after, path));
+                }
+            }
+        }
+        redundant
+    }
+
+    /// Shortest path `from -> ... -> to` with at least one step, optionally avoiding one rule.
+    fn path(&self, from: u32, to: u32, pages: &BTreeSet<u32>, skip: Option<(u32, u32)>) -> Option<Vec<u32>> {
+        let mut parent: HashMap<u32, u32> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            for next in self.successors(current, pages) {
+                if Some((current, next)) == skip || parent.contains_key(&next) {
+                    continue;
+                }
+                parent.insert(next, current);
+                if next == to {
+                    let mut path = vec![to];
+                    let mut page = to;
+                    while let Some(prev) = parent.get(&page).copied() {
+                        path.push(prev);
+                        if prev == from {
+                            break;
+                        }
+                        page = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    /// Each group of mutually dependent pages among `pages` with one cycle through it, found
+    /// with Tarjan's strongly connected components.
+    pub fn cycles(&self, pages: &BTreeSet<u32>) -> Vec<(BTreeSet<u32>, Vec<u32>)> {
+        let mut tarjan = Tarjan { ruleset: self, pages, index: HashMap::new(), low: HashMap::new(), stack: Vec::new(), components: Vec::new() };
+        for page in pages {
+            if !tarjan.index.contains_key(page) {
+                tarjan.visit(*page);
+            }
+        }
+
+        tarjan.components.into_iter()
+            .filter_map(|component| {
+                let start = *component.iter().min()?;
+                let inside = component.into_iter().collect::<BTreeSet<_>>();
+                let cycle = self.path(start, start, &inside, None)?;
+                Some((inside, cycle))
+            })
+            .collect()
+    }
+
+    /// The only order the rules allow for `pages`: they have to be free of cycles, and every
+    /// two pages have to be ordered by some chain of rules between them.
+    pub fn total_order(&self, pages: &BTreeSet<u32>) -> Result<Vec<u32>, Unordered> {
+        if let Some((_, cycle)) = self.cycles(pages).into_iter().next() {
+            return Err(Unordered::Cycle(cycle));
+        }
+
+        // Without cycles, the order is total exactly when each page of a topological order
+        // has a rule to the next one
+        let mut incoming: BTreeMap<u32, usize> = pages.iter().map(|p| (*p, 0)).collect();
+        for page in pages {
+            for next in self.successors(*page, pages) {
+                *incoming.entry(next).or_default() += 1;
+            }
+        }
+        let mut ready = incoming.iter().filter(|(_, c)| **c == 0).map(|(p, _)| *p).collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(pages.len());
+        while let Some(page) = ready.pop() {
+            if let Some(other) = ready.first() {
+                return Err(Unordered::Incomparable(*other, page));
+            }
+            order.push(page);
+            for next in self.successors(page, pages) {
+                let count = incoming.get_mut(&next).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+        Ok(order)
+    }
+}
+
+struct Tarjan<'a> {
+    ruleset: &'a RuleSet,
+    pages: &'a BTreeSet<u32>,
+    index: HashMap<u32, usize>,
+    low: HashMap<u32, usize>,
+    stack: Vec<u32>,
+    /// Components of more than one page, or a page with a rule to itself
+    components: Vec<Vec<u32>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, page: u32) {
+        let idx = self.index.len();
+        self.index.insert(page, idx);
+        self.low.insert(page, idx);
+        self.stack.push(page);
+
+        for next in self.ruleset.successors(page, self.pages) {
+            if !self.index.contains_key(&next) {
+                self.visit(next);
+                let low = self.low[&page].min(self.low[&next]);
+                self.low.insert(page, low);
+            } else if self.stack.contains(&next) {
+                let low = self.low[&page].min(self.index[&next]);
+                self.low.insert(page, low);
+            }
+        }
+
+        if self.low[&page] == self.index[&page] {
+            let mut component = Vec::new();
+            while let Some(top) = self.stack.pop() {
+                component.push(top);
+                if top == page {
+                    break;
+                }
+            }
+            if component.len() > 1 || self.ruleset.successors(page, self.pages).contains(&page) {
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PrintJob, EXAMPLE};
+
+    fn rules(input: &str) -> RuleSet {
+        input.parse::<PrintJob>().unwrap().rules
+    }
+
+    #[test]
+    fn example_is_one_total_order() {
+        let ruleset = rules(EXAMPLE);
+        assert_eq!(ruleset.total_order(&ruleset.pages()), Ok(vec![97, 75, 47, 61, 53, 29, 13]));
+        assert!(ruleset.cycles(&ruleset.pages()).is_empty());
+
+        let closure = ruleset.closure();
+        assert_eq!(closure[&97], BTreeSet::from([13, 29, 47, 53, 61, 75]));
+        assert_eq!(closure[&53], BTreeSet::from([13, 29]));
+        assert!(closure[&13].is_empty());
+        assert_eq!(closure.values().map(BTreeSet::len).sum::<usize>(), 21);
+    }
+
+    #[test]
+    fn example_needs_only_the_chain_of_neighbours() {
+        let redundant = rules(EXAMPLE).redundant_rules();
+        assert_eq!(redundant.len(), 15);
+        let chain = [97, 75, 47, 61, 53, 29, 13];
+        for (before, after, path) in &redundant {
+            assert!(!chain.windows(2).any(|pair| pair == [*before, *after]), "{before}|{after}");
+            assert_eq!((path[0], path[path.len() - 1]), (*before, *after));
+            assert!(path.len() > 2);
+        }
+        assert!(redundant.contains(&(97, 47, vec![97, 75, 47])));
+    }
+
+    /// Cycles are the shortest ones through the smallest page of each group.
+    #[test]
+    fn cyclic_rules() {
+        let ruleset = rules("1|2\n2|3\n3|1\n1|3\n3|4\n5|6\n6|5\n\n1,2,3\n");
+        let pages = ruleset.pages();
+        assert_eq!(ruleset.cycles(&pages), [
+            (BTreeSet::from([1, 2, 3]), vec![1, 3, 1]),
+            (BTreeSet::from([5, 6]), vec![5, 6, 5]),
+        ]);
+        assert_eq!(ruleset.total_order(&pages), Err(Unordered::Cycle(vec![1, 3, 1])));
+        assert_eq!(ruleset.total_order(&BTreeSet::from([3, 4])), Ok(vec![3, 4]));
+        assert_eq!(ruleset.closure()[&2], BTreeSet::from([1, 2, 3, 4]));
+        assert_eq!(ruleset.redundant_rules(), [(1, 3, vec![1, 2, 3])]);
+    }
+
+    #[test]
+    fn incomparable_pages() {
+        let ruleset = rules("1|2\n1|3\n3|4\n\n1,2,3\n");
+        assert_eq!(ruleset.total_order(&ruleset.pages()), Err(Unordered::Incomparable(2, 3)));
+        assert_eq!(ruleset.total_order(&BTreeSet::from([1, 3, 4])), Ok(vec![1, 3, 4]));
+        assert!(ruleset.cycles(&ruleset.pages()).is_empty());
+        assert!(ruleset.redundant_rules().is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::RuleSet;
+
+/// The rule graph, or the part of it between the pages of one update, ready to be written out.
+pub struct Graph {
+    nodes: Vec<u32>,
+    /// `before -> after` and whether the update breaks the rule
+    edges: Vec<(u32, u32, bool)>,
+    /// Pages of the update as printed and after fixing, if it needed a fix
+    update: Option<(Vec<u32>, Option<Vec<u32>>)>,
+}
+
+impl Graph {
+    pub fn new(ruleset: &RuleSet, page_update: Option<&HashMap<u32, u32>>) -> Graph {
+        let Some(page_update) = page_update else {
+            let edges = ruleset.page_rules.iter()
+                .flat_map(|(before, afters)| afters.iter().map(move |after| (*before, *after, false)))
+                .collect::<BTreeSet<_>>();
+            let nodes = edges.iter().flat_map(|(b, a, _)| [*b, *a]).collect::<BTreeSet<_>>();
+            return Graph { nodes: nodes.into_iter().collect(), edges: edges.into_iter().collect(), update: None };
+        };
+
+        let mut printed = page_update.keys().copied().collect::<Vec<_>>();
+        printed.sort_unstable_by_key(|page| page_update[page]);
+
+        let edges = printed.iter()
+            .flat_map(|before| ruleset.rules_within(*before, page_update)
+                .map(move |after| (*before, after, page_update[&after] < page_update[before])))
+            .collect::<BTreeSet<_>>();
+
+        // A cycle leaves nothing to fix towards, the graph still shows the broken rules
+        let fixed = match edges.iter().any(|(_, _, violated)| *violated) {
+            true => ruleset.fix_update_for_ruleset(page_update).ok(),
+            false => None,
+        };
+
+        let mut nodes = printed.clone();
+        nodes.sort_unstable();
+        Graph { nodes, edges: edges.into_iter().collect(), update: Some((printed, fixed)) }
+    }
+
+    /// Position of a page in the update as printed and in the fix, 1-based.
+    fn positions(&self, page: u32) -> Option<(usize, Option<usize>)> {
+        let (printed, fixed) = self.update.as_ref()?;
+        let from = printed.iter().position(|p| *p == page)? + 1;
+        let to = fixed.as_ref().and_then(|f| f.iter().position(|p| *p == page)).map(|to| to + 1);
+        Some((from, to))
+    }
+
+    /// GraphViz source. Broken rules are red; the fixed order is a dashed blue chain and each
+    /// page is labelled with its printed and fixed position.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph rules {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for page in &self.nodes {
+            match self.positions(*page) {
+                Some((from, Some(to))) => out.push_str(&format!("    \"{}\" [label=\"{}\\n{} -> {}\"];\n", page, page, from, to)),
+                Some((from, None)) => out.push_str(&format!("    \"{}\" [label=\"{}\\n{}\"];\n", page, page, from)),
+                None => out.push_str(&format!("    \"{}\";\n", page)),
+            }
+        }
+
+        for (before, after, violated) in &self.edges {
+            match violated {
+                true => out.push_str(&format!("    \"{}\" -> \"{}\" [color=red, penwidth=2];\n", before, after)),
+                false => out.push_str(&format!("    \"{}\" -> \"{}\";\n", before, after)),
+            }
+        }
+
+        if let Some((_, Some(fixed))) = &self.update {
+            for pair in fixed.windows(2) {
+                out.push_str(&format!("    \"{}\" -> \"{}\" [style=dashed, color=blue, constraint=false];\n", pair[0], pair[1]));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Adjacency lists plus the edge list with the broken rules marked.
+    pub fn to_json(&self) -> String {
+        let list = |pages: &[u32]| pages.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ");
+
+        let adjacency = self.nodes.iter().map(|page| {
+            let afters = self.edges.iter().filter(|(b, _, _)| b == page).map(|(_, a, _)| *a).collect::<Vec<_>>();
+            format!("    \"{}\": [{}]", page, list(&afters))
+        }).collect::<Vec<_>>();
+
+        let edges = self.edges.iter()
+            .map(|(b, a, violated)| format!("    {{ \"before\": {}, \"after\": {}, \"violated\": {} }}", b, a, violated))
+            .collect::<Vec<_>>();
+
+        let mut out = format!("{{\n  \"nodes\": [{}],\n  \"adjacency\": {{\n{}\n  }},\n  \"edges\": [\n{}\n  ]",
+            list(&self.nodes), adjacency.join(",\n"), edges.join(",\n"));
+        if let Some((printed, fixed)) = &self.update {
+            out.push_str(&format!(",\n  \"update\": [{}]", list(printed)));
+            match fixed {
+                Some(fixed) => out.push_str(&format!(",\n  \"fixed\": [{}]", list(fixed))),
+                None => out.push_str(",\n  \"fixed\": null"),
+            }
+        }
+        out.push_str("\n}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{positions, PrintJob, EXAMPLE};
+
+    fn graph(input: &str, update: Option<usize>) -> Graph {
+        let job = input.parse::<PrintJob>().unwrap();
+        let page_update = update.map(|n| positions(&job.updates[n]));
+        Graph::new(&job.rules, page_update.as_ref())
+    }
+
+    #[test]
+    fn dot_marks_broken_rules_and_the_fixed_order() {
+        assert_eq!(graph(EXAMPLE, Some(3)).to_dot(), concat!(
+            "digraph rules {\n    rankdir=LR;\n    node [shape=box];\n",
+            "    \"47\" [label=\"47\\n3 -> 3\"];\n",
+            "    \"53\" [label=\"53\\n5 -> 5\"];\n",
+            "    \"61\" [label=\"61\\n4 -> 4\"];\n",
+            "    \"75\" [label=\"75\\n1 -> 2\"];\n",
+            "    \"97\" [label=\"97\\n2 -> 1\"];\n",
+            "    \"47\" -> \"53\";\n    \"47\" -> \"61\";\n    \"61\" -> \"53\";\n",
+            "    \"75\" -> \"47\";\n    \"75\" -> \"53\";\n    \"75\" -> \"61\";\n",
+            "    \"97\" -> \"47\";\n    \"97\" -> \"53\";\n    \"97\" -> \"61\";\n",
+            "    \"97\" -> \"75\" [color=red, penwidth=2];\n",
+            "    \"97\" -> \"75\" [style=dashed, color=blue, constraint=false];\n",
+            "    \"75\" -> \"47\" [style=dashed, color=blue, constraint=false];\n",
+            "    \"47\" -> \"61\" [style=dashed, color=blue, constraint=false];\n",
+            "    \"61\" -> \"53\" [style=dashed, color=blue, constraint=false];\n",
+            "}\n"));
+    }
+
+    #[test]
+    fn json_lists_the_update_and_its_fix() {
+        assert_eq!(graph(EXAMPLE, Some(4)).to_json(), concat!(
+            "{\n  \"nodes\": [13, 29, 61],\n",
+            "  \"adjacency\": {\n    \"13\": [],\n    \"29\": [13],\n    \"61\": [13, 29]\n  },\n",
+            "  \"edges\": [\n",
+            "    { \"before\": 29, \"after\": 13, \"violated\": true },\n",
+            "    { \"before\": 61, \"after\": 13, \"violated\": false },\n",
+            "    { \"before\": 61, \"after\": 29, \"violated\": false }\n",
+            "  ],\n  \"update\": [61, 13, 29],\n  \"fixed\": [61, 29, 13]\n}\n"));
+    }
+
+    #[test]
+    fn whole_rule_set_has_no_positions() {
+        let dot = graph(EXAMPLE, None).to_dot();
+        assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 21);
+        assert!(dot.contains("    \"97\";\n") && !dot.contains("label") && !dot.contains("red"));
+        assert!(!graph(EXAMPLE, None).to_json().contains("update"));
+    }
+
+    #[test]
+    fn cyclic_update_is_exported_without_a_fix() {
+        let cyclic = "1|2\n2|3\n3|1\n\n1,2,3\n";
+        assert_eq!(graph(cyclic, Some(0)).to_json(), concat!(
+            "{\n  \"nodes\": [1, 2, 3],\n",
+            "  \"adjacency\": {\n    \"1\": [2],\n    \"2\": [3],\n    \"3\": [1]\n  },\n",
+            "  \"edges\": [\n",
+            "    { \"before\": 1, \"after\": 2, \"violated\": false },\n",
+            "    { \"before\": 2, \"after\": 3, \"violated\": false },\n",
+            "    { \"before\": 3, \"after\": 1, \"violated\": true }\n",
+            "  ],\n  \"update\": [1, 2, 3],\n  \"fixed\": null\n}\n"));
+        assert_eq!(graph(cyclic, Some(0)).to_dot(), concat!(
+            "digraph rules {\n    rankdir=LR;\n    node [shape=box];\n",
+            "    \"1\" [label=\"1\\n1\"];\n    \"2\" [label=\"2\\n2\"];\n    \"3\" [label=\"3\\n3\"];\n",
+            "    \"1\" -> \"2\";\n    \"2\" -> \"3\";\n",
+            "    \"3\" -> \"1\" [color=red, penwidth=2];\n",
+            "}\n"));
+    }
+}
@@ -0,0 +1,128 @@
+use std::{collections::HashMap, str::FromStr};
+
+use aoc_core::parsing::{parse_at, split_pair};
+
+use crate::{Error, Result, RuleSet};
+
+/// The puzzle input: `X|Y` ordering rules, a blank line, then one comma separated update per
+/// line. Every update has an odd number of distinct pages, so it has a middle page.
+pub struct PrintJob {
+    pub rules: RuleSet,
+    pub updates: Vec<Vec<u32>>,
+}
+
+impl FromStr for PrintJob {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut rules = RuleSet { page_rules: HashMap::new() };
+        let mut updates = Vec::new();
+        let mut in_updates = false;
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.trim();
+
+            // Blank lines end the rules, any number of them, and are ignored after that
+            if line.is_empty() {
+                in_updates |= !rules.page_rules.is_empty();
+                continue;
+            }
+
+            if !in_updates {
+                if line.contains(',') && !line.contains('|') {
+                    return Err(format!("line {}: expected a rule 'X|Y', updates have to follow a blank line", line_no).into());
+                }
+                let (before, after) = split_pair(line, "|", line_no)?;
+                let (before, after) = (parse_at::<u32>(before, line_no)?, parse_at::<u32>(after, line_no)?);
+                if before == after {
+                    return Err(format!("line {}: rule {}|{} orders a page before itself", line_no, before, after).into());
+                }
+
+                let afters = rules.page_rules.entry(before).or_default();
+                if !afters.contains(&after) {
+                    afters.push(after);
+                }
+            } else {
+                if line.contains('|') {
+                    return Err(format!("line {}: rule '{}' after the updates started", line_no, line).into());
+                }
+                updates.push(parse_update(line, line_no)?);
+            }
+        }
+
+        Ok(PrintJob { rules, updates })
+    }
+}
+
+fn parse_update(line: &str, line_no: usize) -> Result<Vec<u32>> {
+    let pages = line.split(',').map(|page| parse_at::<u32>(page, line_no)).collect::<Result<Vec<_>>>()?;
+
+    let mut seen = HashMap::new();
+    for (pos, page) in pages.iter().enumerate() {
+        if let Some(first) = seen.insert(*page, pos) {
+            return Err(format!("line {}: page {} appears twice, at positions {} and {}", line_no, page, first + 1, pos + 1).into());
+        }
+    }
+    if pages.len() % 2 == 0 {
+        return Err(format!("line {}: update has {} pages, so there is no middle page", line_no, pages.len()).into());
+    }
+
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    fn error(input: &str) -> String {
+        input.parse::<PrintJob>().err().expect("input should be rejected").to_string()
+    }
+
+    #[test]
+    fn parses_the_example() {
+        let job = EXAMPLE.parse::<PrintJob>().unwrap();
+        assert_eq!(job.rules.page_rules.values().map(Vec::len).sum::<usize>(), 21);
+        assert_eq!(job.rules.page_rules[&97], [13, 61, 47, 29, 53, 75]);
+        assert_eq!(job.updates.len(), 6);
+        assert_eq!(job.updates[5], [97, 13, 75, 29, 47]);
+    }
+
+    #[test]
+    fn blank_lines_around_the_separator_are_ignored() {
+        let job = "\n1|2\n\n\n1,2,3\n\n2,1,3\n".parse::<PrintJob>().unwrap();
+        assert_eq!(job.updates, [[1, 2, 3], [2, 1, 3]]);
+    }
+
+    #[test]
+    fn duplicate_page() {
+        assert_eq!(error("1|2\n\n1,2,3\n4,5,4\n"), "line 4: page 4 appears twice, at positions 1 and 3");
+    }
+
+    #[test]
+    fn even_length_update() {
+        assert_eq!(error("1|2\n\n1,2\n"), "line 3: update has 2 pages, so there is no middle page");
+    }
+
+    #[test]
+    fn malformed_rule() {
+        assert_eq!(error("1|2\n3-4\n\n1,2,3\n"), "line 2: expected '|' in '3-4'");
+        assert!(error("1|2\n3|x\n\n1,2,3\n").starts_with("line 2: can't parse 'x': "));
+    }
+
+    #[test]
+    fn rule_before_itself() {
+        assert_eq!(error("1|2\n7|7\n\n1,2,3\n"), "line 2: rule 7|7 orders a page before itself");
+    }
+
+    #[test]
+    fn rule_after_the_updates() {
+        assert_eq!(error("1|2\n\n1,2,3\n2|3\n"), "line 4: rule '2|3' after the updates started");
+    }
+
+    #[test]
+    fn missing_blank_line() {
+        assert_eq!(error("1|2\n1,2,3\n"), "line 2: expected a rule 'X|Y', updates have to follow a blank line");
+    }
+}
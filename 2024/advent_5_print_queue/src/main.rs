@@ -0,0 +1,373 @@
+use std::{cmp::Reverse, collections::{BTreeSet, BinaryHeap, HashMap}, fs};
+
+use analysis::Unordered;
+use job::PrintJob;
+
+pub use aoc_core::{Error, Result};
+
+mod analysis;
+mod export;
+mod job;
+mod violations;
+
+/// The rules and updates of the puzzle text.
+#[cfg(test)]
+const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+
+struct RuleSet {
+    page_rules: HashMap<u32, Vec<u32>>,
+}
+
+impl RuleSet {
+
+    fn is_update_valid(&self, page_update: &HashMap<u32, u32> /* Key: Page number, Value: Position */) -> bool {
+        let mut valid = true;
+        for (number, position) in page_update {
+            let page_rules = self.page_rules.get(&number);
+
+            if page_rules.is_some() {
+                for page_rule in page_rules.unwrap() {
+                    let opt_page = page_update.get(page_rule);
+
+                    if opt_page.is_some() && opt_page.unwrap() < position {
+                        valid = false;
+                        break;                            
+                    }
+                }
+            } else {
+                continue;
+            }
+
+            if !valid {
+                break;
+            }
+        }
+
+        valid
+    }
+
+    /// Orders the pages of an invalid update with a topological sort over the rules between
+    /// them. Pages the rules don't constrain keep their relative order.
+    fn fix_update_for_ruleset(&self, page_update: &HashMap<u32, u32> /* Key: Page number, Value: Position */) -> Result<Vec<u32>> {
+        self.order_keeping(page_update, &[])
+    }
+
+    /// Same as `fix_update_for_ruleset`, but the pages of `kept` also stay in the given order.
+    fn order_keeping(&self, page_update: &HashMap<u32, u32>, kept: &[u32]) -> Result<Vec<u32>> {
+        let after = |page: u32| {
+            let next_kept = kept.iter().position(|k| *k == page).and_then(|idx| kept.get(idx + 1)).copied();
+            self.rules_within(page, page_update).chain(next_kept).collect::<Vec<_>>()
+        };
+
+        let mut incoming: HashMap<u32, usize> = page_update.keys().map(|page| (*page, 0)).collect();
+        for page in page_update.keys() {
+            for later in after(*page) {
+                *incoming.get_mut(&later).unwrap() += 1;
+            }
+        }
+
+        // Ready pages by their original position, so the fix moves as little as possible
+        let mut ready = incoming.iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(page, _)| Reverse((page_update[page], *page)))
+            .collect::<BinaryHeap<_>>();
+
+        let mut fixed = Vec::with_capacity(page_update.len());
+        while let Some(Reverse((_, page))) = ready.pop() {
+            fixed.push(page);
+            for later in after(page) {
+                let count = incoming.get_mut(&later).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(Reverse((page_update[&later], later)));
+                }
+            }
+        }
+
+        if fixed.len() < page_update.len() {
+            let stuck = incoming.into_iter().filter(|(_, count)| *count > 0).map(|(page, _)| page).collect::<BTreeSet<_>>();
+            let cycle = match self.cycles(&stuck).into_iter().next() {
+                Some((_, cycle)) => pages_list(&cycle, " -> "),
+                None => pages_list(&stuck.into_iter().collect::<Vec<_>>(), ", "),
+            };
+            return Err(format!("Rules form a cycle: {}", cycle).into());
+        }
+        Ok(fixed)
+    }
+
+    /// Pages of the update that have to come after `page`.
+    fn rules_within<'a>(&'a self, page: u32, page_update: &'a HashMap<u32, u32>) -> impl Iterator<Item = u32> + 'a {
+        self.page_rules.get(&page).into_iter().flatten().copied().filter(|after| page_update.contains_key(after))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
+    process_rulesets()
+}
+
+fn process_rulesets() -> Result<()> {
+    let input = fs::read_to_string("input.txt")?;
+
+    let job = input.parse::<PrintJob>()?;
+    let ruleset = job.rules;
+    let update_lines = job.updates.iter().map(|update| pages_list(update, ",")).collect::<Vec<_>>();
+    let updates = job.updates.iter().map(|update| positions(update)).collect::<Vec<_>>();
+
+
+    // `--analyse closure|redundant|cycles|order` looks at the rule set instead of solving, so
+    // it also works on rules an update can't be fixed with
+    if let Some(what) = aoc_core::params::value("--analyse") {
+        print!("{}", analyse(&what, &ruleset, &updates)?);
+        return Ok(());
+    }
+
+    // `--export dot` or `--export json` writes only the rule graph, so it can be piped into a
+    // file or Graphviz. `--update <n>` narrows it to the pages of the n-th update and marks
+    // what it breaks. Like `--analyse` it doesn't solve, so cyclic rules can be exported too
+    if let Some(format) = aoc_core::params::value("--export") {
+        let page_update = match aoc_core::params::value("--update") {
+            Some(n) => {
+                let n = n.parse::<usize>().map_err(|e| format!("--update {}: {}", n, e))?;
+                Some(updates.get(n.wrapping_sub(1)).ok_or_else(|| format!("There is no update {}, the input has {}", n, updates.len()))?)
+            }
+            None => None,
+        };
+        let graph = export::Graph::new(&ruleset, page_update);
+        match format.as_str() {
+            "dot" => print!("{}", graph.to_dot()),
+            "json" => print!("{}", graph.to_json()),
+            _ => return Err(format!("Unknown export format '{}', expected dot or json", format).into()),
+        }
+        return Ok(());
+    }
+
+    let reporting = aoc_core::params::has_flag("--report");
+    let mut report = String::new();
+
+    let mut valid_updates_result = 0;
+    let mut fixed_invalid_updates_result = 0;
+    for (page_update, line) in updates.iter().zip(&update_lines) {
+        let valid = ruleset.is_update_valid(page_update);
+        let middle_pos = (page_update.len() / 2) as u32;
+
+        if valid {
+            valid_updates_result += *(page_update.iter().find(|(_, v)| **v == middle_pos).unwrap().0);
+        } else {
+            let fixed = ruleset.fix_update_for_ruleset(page_update).map_err(|e| format!("Update {}: {}", line, e))?;
+            fixed_invalid_updates_result += fixed[middle_pos as usize];
+
+            if reporting {
+                report.push_str(&describe_fix(&ruleset, line, page_update)?);
+            }
+        }
+    }
+
+    println!("Part 1 answer: {}", valid_updates_result);
+    println!("Part 2 answer: {}", fixed_invalid_updates_result);
+    print!("{}", report);
+
+    return Ok(());
+}
+
+/// Page number to position, the form `RuleSet` checks updates in.
+fn positions(update: &[u32]) -> HashMap<u32, u32> {
+    update.iter().enumerate().map(|(idx, page)| (*page, idx as u32)).collect()
+}
+
+fn pages_list(pages: &[u32], separator: &str) -> String {
+    pages.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(separator)
+}
+
+/// One record per line, `<kind> <subject>: <details>`, then a summary line.
+fn analyse(what: &str, ruleset: &RuleSet, updates: &[HashMap<u32, u32>]) -> Result<String> {
+    let rule_count = ruleset.page_rules.values().map(Vec::len).sum::<usize>();
+    let mut out = String::new();
+
+    match what {
+        "closure" => {
+            let closure = ruleset.closure();
+            for (page, after) in &closure {
+                out.push_str(&format!("after {}: {}\n", page, pages_list(&after.iter().copied().collect::<Vec<_>>(), " ")));
+            }
+            let pairs = closure.values().map(BTreeSet::len).sum::<usize>();
+            out.push_str(&format!("closure: {} ordered pairs from {} rules\n", pairs, rule_count));
+        }
+        "redundant" => {
+            let redundant = ruleset.redundant_rules();
+            for (before, after, path) in &redundant {
+                out.push_str(&format!("redundant {}|{}: via {}\n", before, after, pages_list(path, " -> ")));
+            }
+            out.push_str(&format!("redundant: {} of {} rules\n", redundant.len(), rule_count));
+        }
+        "cycles" => {
+            let global = ruleset.cycles(&ruleset.pages());
+            for (group, cycle) in &global {
+                out.push_str(&format!("cycle rules: {} ({} pages depend on each other)\n", pages_list(cycle, " -> "), group.len()));
+            }
+            let mut cyclic_updates = 0;
+            for (idx, page_update) in updates.iter().enumerate() {
+                let cycles = ruleset.cycles(&page_update.keys().copied().collect());
+                cyclic_updates += usize::from(!cycles.is_empty());
+                for (group, cycle) in cycles {
+                    out.push_str(&format!("cycle update {}: {} ({} pages depend on each other)\n", idx + 1, pages_list(&cycle, " -> "), group.len()));
+                }
+            }
+            out.push_str(&format!("cycles: {} in the rule set, {} of {} updates\n", global.len(), cyclic_updates, updates.len()));
+        }
+        "order" => {
+            let pages = updates.iter().flat_map(|u| u.keys().copied()).collect::<BTreeSet<_>>();
+            match ruleset.total_order(&pages) {
+                Ok(order) => out.push_str(&format!("order pages: {}\n", pages_list(&order, " "))),
+                Err(Unordered::Cycle(cycle)) => out.push_str(&format!("unordered pages: cycle {}\n", pages_list(&cycle, " -> "))),
+                Err(Unordered::Incomparable(a, b)) => out.push_str(&format!("unordered pages: no rule orders {} and {}\n", a, b)),
+            }
+            let mut ordered = 0;
+            for (idx, page_update) in updates.iter().enumerate() {
+                match ruleset.total_order(&page_update.keys().copied().collect()) {
+                    Ok(_) => ordered += 1,
+                    Err(Unordered::Cycle(cycle)) => out.push_str(&format!("unordered update {}: cycle {}\n", idx + 1, pages_list(&cycle, " -> "))),
+                    Err(Unordered::Incomparable(a, b)) => out.push_str(&format!("unordered update {}: no rule orders {} and {}\n", idx + 1, a, b)),
+                }
+            }
+            out.push_str(&format!("order: {} pages, total order {}, {} of {} updates totally ordered\n",
+                pages.len(), if ruleset.total_order(&pages).is_ok() { "yes" } else { "no" }, ordered, updates.len()));
+        }
+        _ => return Err(format!("Unknown analysis '{}', expected closure, redundant, cycles or order", what).into()),
+    }
+
+    Ok(out)
+}
+
+/// Which rules an invalid update breaks, and the fewest moves that fix it.
+fn describe_fix(ruleset: &RuleSet, line: &str, page_update: &HashMap<u32, u32>) -> Result<String> {
+    let broken = ruleset.violations(page_update).iter()
+        .map(|v| format!("{}|{} ({} at {}, {} at {})", v.before, v.after, v.before, v.before_pos + 1, v.after, v.after_pos + 1))
+        .collect::<Vec<_>>();
+    let (fixed, moves) = ruleset.minimal_moves(page_update)?;
+    let moves = moves.iter()
+        .map(|m| match m.after {
+            Some(after) => format!("{} (at {}) after {}", m.page, m.from + 1, after),
+            None => format!("{} (at {}) to the front", m.page, m.from + 1),
+        })
+        .collect::<Vec<_>>();
+    let fixed = fixed.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+
+    Ok(format!("{}: breaks {}\n  fix: move {} -> {}\n", line, broken.join(", "), moves.join(", "), fixed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix_error(input: &str) -> String {
+        let job = input.parse::<PrintJob>().unwrap();
+        job.rules.fix_update_for_ruleset(&positions(&job.updates[0])).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn cycle_starts_at_its_smallest_page() {
+        assert_eq!(fix_error("2|3\n3|1\n1|2\n\n3,2,1\n"), "Rules form a cycle: 1 -> 2 -> 3 -> 1");
+    }
+
+    #[test]
+    fn pages_waiting_on_a_cycle_are_not_part_of_it() {
+        let input = "1|9\n9|5\n5|7\n7|9\n7|2\n\n2,7,5,9,1\n";
+        assert_eq!(fix_error(input), "Rules form a cycle: 5 -> 7 -> 9 -> 5");
+
+        // `--analyse cycles` names the same cycle
+        let job = input.parse::<PrintJob>().unwrap();
+        let cycles = job.rules.cycles(&job.rules.pages());
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].1, [5, 7, 9, 5]);
+    }
+}
+
+/*
+--- Day 5: Print Queue ---
+Satisfied with their search on Ceres, the squadron of scholars suggests subsequently scanning the stationery stacks of sub-basement 17.
+
+The North Pole printing department is busier than ever this close to Christmas, and while The Historians continue their search of this historically significant facility, an Elf operating a very familiar printer beckons you over.
+
+The Elf must recognize you, because they waste no time explaining that the new sleigh launch safety manual updates won't print correctly. Failure to update the safety manuals would be dire indeed, so you offer your services.
+
+Safety protocols clearly indicate that new pages for the safety manuals must be printed in a very specific order. The notation X|Y means that if both page number X and page number Y are to be produced as part of an update, page number X must be printed at some point before page number Y.
+
+The Elf has for you both the page ordering rules and the pages to produce in each update (your puzzle input), but can't figure out whether each update has the pages in the right order.
+
+For example:
+
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+The first section specifies the page ordering rules, one per line. The first rule, 47|53, means that if an update includes both page number 47 and page number 53, then page number 47 must be printed at some point before page number 53. (47 doesn't necessarily need to be immediately before 53; other pages are allowed to be between them.)
+
+The second section specifies the page numbers of each update. Because most safety manuals are different, the pages needed in the updates are different too. The first update, 75,47,61,53,29, means that the update consists of page numbers 75, 47, 61, 53, and 29.
+
+To get the printers going as soon as possible, start by identifying which updates are already in the right order.
+
+In the above example, the first update (75,47,61,53,29) is in the right order:
+
+75 is correctly first because there are rules that put each other page after it: 75|47, 75|61, 75|53, and 75|29.
+47 is correctly second because 75 must be before it (75|47) and every other page must be after it according to 47|61, 47|53, and 47|29.
+61 is correctly in the middle because 75 and 47 are before it (75|61 and 47|61) and 53 and 29 are after it (61|53 and 61|29).
+53 is correctly fourth because it is before page number 29 (53|29).
+29 is the only page left and so is correctly last.
+Because the first update does not include some page numbers, the ordering rules involving those missing page numbers are ignored.
+
+The second and third updates are also in the correct order according to the rules. Like the first update, they also do not include every page number, and so only some of the ordering rules apply - within each update, the ordering rules that involve missing page numbers are not used.
+
+The fourth update, 75,97,47,61,53, is not in the correct order: it would print 75 before 97, which violates the rule 97|75.
+
+The fifth update, 61,13,29, is also not in the correct order, since it breaks the rule 29|13.
+
+The last update, 97,13,75,29,47, is not in the correct order due to breaking several rules.
+
+For some reason, the Elves also need to know the middle page number of each update being printed. Because you are currently only printing the correctly-ordered updates, you will need to find the middle page number of each correctly-ordered update. In the above example, the correctly-ordered updates are:
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+These have middle page numbers of 61, 53, and 29 respectively. Adding these page numbers together gives 143.
+
+Of course, you'll need to be careful: the actual list of page ordering rules is bigger and more complicated than the above example.
+
+Determine which updates are already in the correct order. What do you get if you add up the middle page number from those correctly-ordered updates?
+
+--- Part Two ---
+While the Elves get to work printing the correctly-ordered updates, you have a little time to fix the rest of them.
+
+For each of the incorrectly-ordered updates, use the page ordering rules to put the page numbers in the right order. For the above example, here are the three incorrectly-ordered updates and their correct orderings:
+
+75,97,47,61,53 becomes 97,75,47,61,53.
+61,13,29 becomes 61,29,13.
+97,13,75,29,47 becomes 97,75,47,29,13.
+After taking only the incorrectly-ordered updates and ordering them correctly, their middle page numbers are 47, 29, and 47. Adding these together produces 123.
+
+Find the updates which are not in the correct order. What do you get if you add up the middle page numbers after correctly ordering just those updates?
+*/
\ No newline at end of file
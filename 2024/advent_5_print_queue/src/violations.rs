@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use crate::{Result, RuleSet};
+
+/// A broken rule `before|after`: `after` was printed first. Positions are 0-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub before: u32,
+    pub after: u32,
+    pub before_pos: u32,
+    pub after_pos: u32,
+}
+
+/// A page taken out of position `from` and put back right after page `after`, or at the
+/// front. Applied in the listed order they turn the update into the fixed order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub page: u32,
+    pub from: u32,
+    pub after: Option<u32>,
+}
+
+impl RuleSet {
+    /// Every rule between two pages of the update that the update breaks, in page order.
+    pub fn violations(&self, page_update: &HashMap<u32, u32>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for page in pages_in_order(page_update) {
+            for after in self.rules_within(page, page_update) {
+                if page_update[&after] < page_update[&page] {
+                    violations.push(Violation { before: page, after, before_pos: page_update[&page], after_pos: page_update[&after] });
+                }
+            }
+        }
+        violations
+    }
+
+    /// The fewest pages to move, and where they go, to make the update valid.
+    ///
+    /// The pages left alone must not contradict each other, counting rules that hold through
+    /// other pages too. Page `i` conflicts with a later page `j` when the rules put `j` first;
+    /// that relation is transitive, so the largest conflict-free set is a maximum antichain and
+    /// Dilworth's theorem gives it from a maximum bipartite matching.
+    pub fn minimal_moves(&self, page_update: &HashMap<u32, u32>) -> Result<(Vec<u32>, Vec<Move>)> {
+        let pages = pages_in_order(page_update);
+        let n = pages.len();
+        let reach = self.reachability(&pages, page_update);
+        let conflicts = |i: usize, j: usize| i < j && reach[j][i];
+
+        let mut matched_left = vec![None; n];
+        let mut matched_right = vec![None; n];
+        for left in 0..n {
+            let mut visited = vec![false; n];
+            augment(left, &conflicts, &mut visited, &mut matched_left, &mut matched_right);
+        }
+
+        // König: walk alternating paths from unmatched left vertices, the antichain is
+        // everything whose left copy was reached and whose right copy wasn't.
+        let mut left_seen = vec![false; n];
+        let mut right_seen = vec![false; n];
+        let mut stack = (0..n).filter(|l| matched_left[*l].is_none()).collect::<Vec<_>>();
+        while let Some(left) = stack.pop() {
+            if left_seen[left] {
+                continue;
+            }
+            left_seen[left] = true;
+            for right in (0..n).filter(|r| conflicts(left, *r) && matched_left[left] != Some(*r)) {
+                right_seen[right] = true;
+                if let Some(next) = matched_right[right] {
+                    stack.push(next);
+                }
+            }
+        }
+        let kept = (0..n).filter(|i| left_seen[*i] && !right_seen[*i]).map(|i| pages[i]).collect::<Vec<_>>();
+
+        let fixed = self.order_keeping(page_update, &kept)?;
+        let moves = fixed.iter().enumerate()
+            .filter(|(_, page)| !kept.contains(page))
+            .map(|(to, page)| Move { page: *page, from: page_update[page], after: to.checked_sub(1).map(|prev| fixed[prev]) })
+            .collect();
+        Ok((fixed, moves))
+    }
+
+    /// `reach[i][j]`: the rules, directly or through other pages of the update, put page `i`
+    /// before page `j`.
+    fn reachability(&self, pages: &[u32], page_update: &HashMap<u32, u32>) -> Vec<Vec<bool>> {
+        let mut reach = vec![vec![false; pages.len()]; pages.len()];
+        for (start, page) in pages.iter().enumerate() {
+            let mut stack = vec![*page];
+            while let Some(current) = stack.pop() {
+                for after in self.rules_within(current, page_update) {
+                    let idx = page_update[&after] as usize;
+                    if !reach[start][idx] {
+                        reach[start][idx] = true;
+                        stack.push(after);
+                    }
+                }
+            }
+        }
+        reach
+    }
+}
+
+fn pages_in_order(page_update: &HashMap<u32, u32>) -> Vec<u32> {
+    let mut pages = page_update.keys().copied().collect::<Vec<_>>();
+    pages.sort_unstable_by_key(|page| page_update[page]);
+    pages
+}
+
+/// Kuhn's augmenting path step for the bipartite graph of conflicting page pairs.
+fn augment(
+    left: usize,
+    conflicts: &impl Fn(usize, usize) -> bool,
+    visited: &mut [bool],
+    matched_left: &mut [Option<usize>],
+    matched_right: &mut [Option<usize>],
+) -> bool {
+    for right in 0..visited.len() {
+        if !conflicts(left, right) || visited[right] {
+            continue;
+        }
+        visited[right] = true;
+        let free = match matched_right[right] {
+            None => true,
+            Some(other) => augment(other, conflicts, visited, matched_left, matched_right),
+        };
+        if free {
+            matched_left[left] = Some(right);
+            matched_right[right] = Some(left);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{positions, PrintJob, EXAMPLE};
+
+
+    /// Tries every set of pages to leave alone, largest first, for checking `minimal_moves`.
+    fn minimal_moves_brute_force(ruleset: &RuleSet, page_update: &HashMap<u32, u32>) -> usize {
+        let pages = pages_in_order(page_update);
+        let n = pages.len();
+        let reach = ruleset.reachability(&pages, page_update);
+
+        (0u32..1 << n)
+            .filter(|set| (0..n).all(|i| (i + 1..n).all(|j| set & (1 << i) == 0 || set & (1 << j) == 0 || !reach[j][i])))
+            .map(|set| n - set.count_ones() as usize)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// The moves have to be as few as brute force finds, turn the update into the fixed
+    /// order when applied one by one, and leave no broken rule.
+    fn check_moves(ruleset: &RuleSet, update: &[u32]) {
+        let page_update = positions(update);
+        let (fixed, moves) = ruleset.minimal_moves(&page_update).unwrap();
+        assert!(ruleset.violations(&positions(&fixed)).is_empty(), "{update:?} fixed as {fixed:?}");
+        assert_eq!(moves.len(), minimal_moves_brute_force(ruleset, &page_update), "{update:?}");
+
+        let mut order = update.to_vec();
+        for m in &moves {
+            assert_eq!(update[m.from as usize], m.page);
+            order.retain(|page| *page != m.page);
+            let to = m.after.map_or(0, |after| order.iter().position(|p| *p == after).unwrap() + 1);
+            order.insert(to, m.page);
+        }
+        assert_eq!(order, fixed, "{update:?}");
+    }
+
+    #[test]
+    fn puzzle_example_moves_match_brute_force() {
+        let job = EXAMPLE.parse::<PrintJob>().unwrap();
+        for update in &job.updates {
+            check_moves(&job.rules, update);
+        }
+    }
+
+    /// Random acyclic rule sets: pages get a hidden rank and rules only ever point from a
+    /// lower rank to a higher one. Updates are shuffled subsets of the pages.
+    #[test]
+    fn minimal_moves_match_brute_force_on_generated_updates() {
+        let mut state = 2024u64;
+        let mut below = |bound: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound as u64) as usize
+        };
+
+        for _ in 0..100 {
+            let page_count = 3 + below(14);
+            let mut ranked = (10..10 + page_count as u32).collect::<Vec<_>>();
+            for i in (1..ranked.len()).rev() {
+                ranked.swap(i, below(i + 1));
+            }
+            let density = 1 + below(4);
+            let mut page_rules: HashMap<u32, Vec<u32>> = HashMap::new();
+            for i in 0..page_count {
+                for j in i + 1..page_count {
+                    if below(4) < density {
+                        page_rules.entry(ranked[i]).or_default().push(ranked[j]);
+                    }
+                }
+            }
+            let ruleset = RuleSet { page_rules };
+
+            for _ in 0..10 {
+                let mut update = ranked.clone();
+                for i in (1..update.len()).rev() {
+                    update.swap(i, below(i + 1));
+                }
+                update.truncate(1 + below(page_count));
+                check_moves(&ruleset, &update);
+            }
+        }
+    }
+}
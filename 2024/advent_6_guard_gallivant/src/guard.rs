@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use crate::{Direction, Map};
+
+/// A guard as drawn on the map: `^`, `>`, `v` or `<` at its starting cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Guard {
+    pub start: (i32, i32),
+    pub heading: Direction,
+}
+
+/// Where a patrol ends up. Steps count moves from one cell to the next, turning is free.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Leaves the map from the edge cell `at`
+    Exits { at: (i32, i32), heading: Direction, steps: usize },
+    /// Reaches the loop after `steps` and then goes round it every `cycle` steps
+    Loops { steps: usize, cycle: usize },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Patrol {
+    pub outcome: Outcome,
+    /// The earlier guard whose route this one walked into, with shared detection
+    pub joined: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Detection {
+    /// Every guard walks its whole route by itself
+    Independent,
+    /// Guards remember each other's routes: walking into one means the same ending
+    Shared,
+}
+
+type State = ((i32, i32), Direction);
+
+/// Walks one guard until it leaves the map or repeats a position and heading.
+pub fn patrol(map: &Map, guard: &Guard) -> Outcome {
+    walk(map, guard, 0, &mut HashMap::new(), &[]).outcome
+}
+
+/// Walks every guard of the map, in reading order. Guards don't block each other.
+pub fn patrols(map: &Map, detection: Detection) -> Vec<Patrol> {
+    let mut seen = HashMap::new();
+    let mut patrols = Vec::with_capacity(map.guards.len());
+    for (idx, guard) in map.guards.iter().enumerate() {
+        if detection == Detection::Independent {
+            seen.clear();
+        }
+        let patrol = walk(map, guard, idx, &mut seen, &patrols);
+        patrols.push(patrol);
+    }
+    patrols
+}
+
+/// `seen` maps each state walked so far to the guard and the step it was at, `earlier` holds
+/// the outcomes of the guards before this one.
+fn walk(map: &Map, guard: &Guard, idx: usize, seen: &mut HashMap<State, (usize, usize)>, earlier: &[Patrol]) -> Patrol {
+    let mut pos = guard.start;
+    let mut heading = guard.heading;
+    let mut steps = 0;
+
+    loop {
+        match seen.get(&(pos, heading)).copied() {
+            Some((owner, at)) if owner == idx => {
+                return Patrol { outcome: Outcome::Loops { steps: at, cycle: steps - at }, joined: None };
+            }
+            // From here on this guard walks exactly what `owner` walked from step `at`
+            Some((owner, at)) => {
+                let outcome = match earlier[owner].outcome {
+                    Outcome::Exits { at: exit, heading, steps: total } => Outcome::Exits { at: exit, heading, steps: steps + total - at },
+                    Outcome::Loops { steps: before, cycle } => Outcome::Loops { steps: steps + before.saturating_sub(at), cycle },
+                };
+                return Patrol { outcome, joined: Some(owner) };
+            }
+            None => _ = seen.insert((pos, heading), (idx, steps)),
+        }
+
+        let ahead = heading.step(pos);
+        if !map.guard_is_in_map(ahead) {
+            return Patrol { outcome: Outcome::Exits { at: pos, heading, steps }, joined: None };
+        }
+        if map.obstructions.contains(&ahead) {
+            heading = heading.turn_clockwise();
+        } else {
+            pos = ahead;
+            steps += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::parse_map;
+
+    fn outcomes(patrols: &[Patrol]) -> Vec<Outcome> {
+        patrols.iter().map(|p| p.outcome).collect()
+    }
+
+    #[test]
+    fn joining_a_route_that_exits() {
+        let map = parse_map("#...\n.>..\n^...\n").unwrap();
+        let exit = Outcome::Exits { at: (1, 3), heading: Direction::East, steps: 2 };
+        let joined = Outcome::Exits { at: (1, 3), heading: Direction::East, steps: 4 };
+
+        let shared = patrols(&map, Detection::Shared);
+        assert_eq!(shared, [Patrol { outcome: exit, joined: None }, Patrol { outcome: joined, joined: Some(0) }]);
+        assert_eq!(outcomes(&patrols(&map, Detection::Independent)), [exit, joined]);
+    }
+
+    #[test]
+    fn joining_a_loop() {
+        let map = parse_map(".#..\n...#\n#^.<\n..#.\n").unwrap();
+        let shared = patrols(&map, Detection::Shared);
+        assert_eq!(shared[0], Patrol { outcome: Outcome::Loops { steps: 0, cycle: 4 }, joined: None });
+        assert_eq!(shared[1], Patrol { outcome: Outcome::Loops { steps: 1, cycle: 4 }, joined: Some(0) });
+        assert_eq!(outcomes(&patrols(&map, Detection::Independent)), outcomes(&shared));
+    }
+
+    /// Sharing routes is only a shortcut: every guard has to end up exactly where walking on
+    /// its own takes it.
+    #[test]
+    fn shared_detection_matches_independent_walks_on_generated_maps() {
+        let mut state = 2024u64;
+        let mut below = |bound: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound as u64) as i32
+        };
+
+        let mut joined = 0;
+        for _ in 0..300 {
+            let size = (3 + below(14), 3 + below(14));
+            let density = below(35);
+            let mut map = Map { visited: HashSet::new(), obstructions: HashSet::new(), guards: Vec::new(), size };
+            for row in 0..size.0 {
+                for col in 0..size.1 {
+                    match below(100) {
+                        roll if roll < density => _ = map.obstructions.insert((row, col)),
+                        roll if roll < density + 8 => map.guards.push(Guard { start: (row, col), heading: Direction::ALL[below(4) as usize] }),
+                        _ => {}
+                    }
+                }
+            }
+
+            let shared = patrols(&map, Detection::Shared);
+            joined += shared.iter().filter(|p| p.joined.is_some()).count();
+            for (guard, walked) in map.guards.iter().zip(&shared) {
+                assert_eq!(walked.outcome, patrol(&map, guard), "{:?} {:?}", guard, map.obstructions);
+            }
+            assert_eq!(outcomes(&patrols(&map, Detection::Independent)), outcomes(&shared));
+        }
+        assert!(joined > 100, "only {joined} generated guards join another route");
+    }
+}
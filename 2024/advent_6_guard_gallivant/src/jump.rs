@@ -0,0 +1,208 @@
+use crate::{guard::Guard, Direction, Map};
+
+/// For every cell and heading, where walking straight ends: the last cell before the next
+/// obstruction, or `None` when the guard walks off the map first.
+pub struct JumpTable {
+    size: (i32, i32),
+    stops: Vec<Option<(i32, i32)>>,
+}
+
+impl JumpTable {
+    pub fn new(map: &Map) -> JumpTable {
+        let (rows, cols) = map.size;
+        let mut table = JumpTable { size: map.size, stops: vec![None; (rows * cols) as usize * 4] };
+
+        for dir in Direction::ALL {
+            let (dr, dc) = dir.delta();
+            // Sweep against the heading, so the cell ahead is always filled in first
+            let cols = (0..cols).map(|col| if dc > 0 { cols - 1 - col } else { col }).collect::<Vec<_>>();
+            let rows = (0..rows).map(|row| if dr > 0 { rows - 1 - row } else { row });
+            for row in rows {
+                for col in &cols {
+                    let pos = (row, *col);
+                    let ahead = dir.step(pos);
+                    let stop = if !map.guard_is_in_map(ahead) {
+                        None
+                    } else if map.obstructions.contains(&ahead) {
+                        Some(pos)
+                    } else {
+                        table.stops[table.index(ahead, &dir)]
+                    };
+                    let idx = table.index(pos, &dir);
+                    table.stops[idx] = stop;
+                }
+            }
+        }
+        table
+    }
+
+    fn index(&self, pos: (i32, i32), dir: &Direction) -> usize {
+        let heading = match dir {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
+        };
+        ((pos.0 * self.size.1 + pos.1) as usize) * 4 + heading
+    }
+
+    /// Where walking from `pos` stops with one extra obstruction at `block`. The table stays
+    /// as it is: the block only matters when it's ahead and closer than the stop.
+    pub fn jump(&self, pos: (i32, i32), dir: &Direction, block: (i32, i32)) -> Option<(i32, i32)> {
+        let stop = self.stops[self.index(pos, dir)];
+        let (dr, dc) = dir.delta();
+        let distance = |to: (i32, i32)| {
+            let (or, oc) = (to.0 - pos.0, to.1 - pos.1);
+            match or * dc == oc * dr {
+                true => Some(or * dr + oc * dc),
+                false => None,
+            }
+        };
+
+        match distance(block) {
+            Some(to_block) if to_block >= 1 && stop.is_none_or(|stop| distance(stop) >= Some(to_block)) => {
+                Some((block.0 - dr, block.1 - dc))
+            }
+            _ => stop,
+        }
+    }
+}
+
+/// Counts the cells where one more obstruction traps the guard in a loop.
+///
+/// Walks the original route once. The first time it's about to enter a cell, the route up to
+/// there can't have been affected by an obstruction in that cell, so the check for that cell
+/// starts right there and jumps from one turn to the next.
+pub fn count_loop_blocks(map: &Map, guard: &Guard) -> usize {
+    let table = JumpTable::new(map);
+    let mut tried = vec![false; (map.size.0 * map.size.1) as usize];
+    // Stamped with the block being tried, so nothing has to be cleared between blocks
+    let mut seen = vec![0u32; table.stops.len()];
+    let cell = |pos: (i32, i32)| (pos.0 * map.size.1 + pos.1) as usize;
+
+    let mut viable_blocks = 0;
+    let mut guard_pos = guard.start;
+    let mut guard_dir = guard.heading;
+    tried[cell(guard_pos)] = true;
+
+    loop {
+        let block = guard_dir.step(guard_pos);
+        if !map.guard_is_in_map(block) {
+            break;
+        }
+        if map.obstructions.contains(&block) {
+            guard_dir = guard_dir.turn_clockwise();
+            continue;
+        }
+
+        if !tried[cell(block)] {
+            tried[cell(block)] = true;
+            let stamp = cell(block) as u32 + 1;
+            if loops(&table, &mut seen, stamp, guard_pos, guard_dir, block) {
+                viable_blocks += 1;
+            }
+        }
+        guard_pos = block;
+    }
+
+    viable_blocks
+}
+
+/// Whether the guard, standing at `pos` facing the new obstruction at `block`, ends up in a
+/// loop. Only the turning points are recorded.
+fn loops(table: &JumpTable, seen: &mut [u32], stamp: u32, mut pos: (i32, i32), mut dir: Direction, block: (i32, i32)) -> bool {
+    loop {
+        dir = dir.turn_clockwise();
+        let idx = table.index(pos, &dir);
+        if seen[idx] == stamp {
+            return true;
+        }
+        seen[idx] = stamp;
+
+        match table.jump(pos, &dir, block) {
+            Some(stop) => pos = stop,
+            None => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{guard::{self, Outcome}, parse_map};
+
+    const EXAMPLE: &str = "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#...\n";
+
+    /// Simulates the whole walk cell by cell for every candidate obstruction on the route.
+    fn count_loop_blocks_brute_force(map: &Map, guard: &Guard) -> usize {
+        let mut route = HashSet::new();
+        let (mut pos, mut dir) = (guard.start, guard.heading);
+        while map.guard_is_in_map(pos) {
+            route.insert(pos);
+            match dir.step(pos) {
+                ahead if map.obstructions.contains(&ahead) => dir = dir.turn_clockwise(),
+                ahead => pos = ahead,
+            }
+        }
+
+        route.iter().filter(|block| **block != guard.start).filter(|block| {
+            let mut seen = HashSet::new();
+            let (mut pos, mut dir) = (guard.start, guard.heading);
+            while map.guard_is_in_map(pos) {
+                if !seen.insert((pos, dir)) {
+                    return true;
+                }
+                match dir.step(pos) {
+                    ahead if ahead == **block || map.obstructions.contains(&ahead) => dir = dir.turn_clockwise(),
+                    ahead => pos = ahead,
+                }
+            }
+            false
+        }).count()
+    }
+
+    #[test]
+    fn example_has_six_loop_blocks() {
+        let map = parse_map(EXAMPLE).unwrap();
+        let guard = map.first_guard().unwrap();
+        assert_eq!(count_loop_blocks(&map, &guard), 6);
+        assert_eq!(count_loop_blocks_brute_force(&map, &guard), 6);
+    }
+
+    /// Random maps with a guard facing any way. Only routes that leave the map count, the
+    /// puzzle has no answer for the others.
+    #[test]
+    fn jump_tables_match_brute_force_on_generated_maps() {
+        let mut state = 2024u64;
+        let mut below = |bound: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound as u64) as i32
+        };
+
+        let mut exiting = 0;
+        for _ in 0..400 {
+            let size = (3 + below(14), 3 + below(14));
+            let density = below(35);
+            let mut map = Map { visited: HashSet::new(), obstructions: HashSet::new(), guards: Vec::new(), size };
+            for row in 0..size.0 {
+                for col in 0..size.1 {
+                    if below(100) < density {
+                        map.obstructions.insert((row, col));
+                    }
+                }
+            }
+            let start = (below(size.0 as usize), below(size.1 as usize));
+            map.obstructions.remove(&start);
+            let guard = Guard { start, heading: Direction::ALL[below(4) as usize] };
+            map.guards.push(guard);
+
+            if let Outcome::Exits { .. } = guard::patrol(&map, &guard) {
+                exiting += 1;
+                assert_eq!(count_loop_blocks(&map, &guard), count_loop_blocks_brute_force(&map, &guard), "{:?} {:?}", guard, map.obstructions);
+            }
+        }
+        assert!(exiting > 200, "only {exiting} generated routes leave the map");
+    }
+}
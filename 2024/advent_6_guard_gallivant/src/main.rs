@@ -1,41 +1,16 @@
 use std::{collections::HashSet, fs};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
-
-#[derive(PartialEq, Eq, Hash, Clone)]
-enum Dir {
-    East,
-    West,
-    South,
-    North
-}
-
-impl Dir {
-    fn rotate(&self) -> Dir {
-        match self {
-            Dir::North => Dir::West,
-            Dir::West => Dir::South,
-            Dir::South => Dir::East,
-            Dir::East => Dir::North,
-        }
-    }
-
-    fn move_towards_direction(&self, curr: (i32, i32)) -> (i32, i32) {
-        match self {
-            Dir::North => (curr.0, curr.1 - 1),
-            Dir::South => (curr.0, curr.1 + 1),
-            Dir::East => (curr.0 -1, curr.1),
-            Dir::West => (curr.0 + 1, curr.1),
-        }
-    }
-}
+pub use aoc_core::{Direction, Error, Result};
+use guard::{Detection, Guard, Outcome, Patrol};
 
+mod guard;
+mod jump;
 
+/// Positions are `(row, col)`. Cells where a guard starts are free floor for every guard.
 struct Map {
     visited: HashSet<(i32, i32)>,
     obstructions: HashSet<(i32, i32)>,
-    guard_start: (i32, i32),
+    guards: Vec<Guard>,
     size: (i32, i32),
 }
 
@@ -43,44 +18,72 @@ impl Map {
     fn guard_is_in_map(&self, guard_pos: (i32, i32)) -> bool {
         guard_pos.0 >= 0 && guard_pos.0 < self.size.0 && guard_pos.1 >= 0 && guard_pos.1 < self.size.1
     }
+
+    /// The puzzle answers are about the first guard in reading order.
+    fn first_guard(&self) -> Result<Guard> {
+        self.guards.first().copied().ok_or_else(|| "There is no guard on the map".into())
+    }
 }
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     let map = get_map()?;
 
-    let map = part1(map);
-    _ = part2(map.unwrap());
+    // `--guards` reports where every guard ends up, `--shared` lets guards that walk into
+    // another guard's route take its outcome instead of walking it again
+    let detection = match aoc_core::params::has_flag("--shared") {
+        true => Detection::Shared,
+        false => Detection::Independent,
+    };
+    if aoc_core::params::has_flag("--guards") {
+        print!("{}", report(&map, &guard::patrols(&map, detection)));
+    }
+
+    let map = part1(map)?;
+    part2(&map)?;
 
     Ok(())
 }
 
 fn get_map() -> Result<Map> {
-    let input = fs::read_to_string("input.txt")?;
-    let mut map = Map { obstructions: HashSet::new(), guard_start: (-1, -1), size: (-1, -1), visited: HashSet::new() };
-    for (y, line) in input.lines().enumerate() {
-        for (ch, coord) in line.chars().enumerate().filter(|(_, ch)| *ch != '.').map(|(x, ch)| (ch, (x as i32, y as i32))) {
-            if ch == '^' {
-                map.guard_start = coord;
-            } else {
-                map.obstructions.insert(coord);
+    parse_map(&fs::read_to_string("input.txt")?)
+}
+
+fn parse_map(input: &str) -> Result<Map> {
+    let mut map = Map { obstructions: HashSet::new(), guards: Vec::new(), size: (-1, -1), visited: HashSet::new() };
+    for (row, line) in input.lines().enumerate() {
+        for (col, ch) in line.chars().enumerate() {
+            let pos = (row as i32, col as i32);
+            match ch {
+                '.' => {}
+                '#' => _ = map.obstructions.insert(pos),
+                _ => match Direction::from_char(ch) {
+                    Some(heading) => map.guards.push(Guard { start: pos, heading }),
+                    None => return Err(format!("line {}: unexpected '{}', expected '.', '#' or a guard '^', '>', 'v', '<'", row + 1, ch).into()),
+                },
             }
-        };
+        }
     }
-    map.size = (input.lines().next().unwrap().len() as i32, input.lines().count() as i32);
+    map.size = (input.lines().count() as i32, input.lines().next().unwrap_or("").len() as i32);
     Ok(map)
 }
 
 fn part1(mut map: Map) -> Result<Map> {
-    let mut guard_pos = map.guard_start;
-    let mut guard_dir = Dir::North;
+    let guard = map.first_guard()?;
+    if let Outcome::Loops { .. } = guard::patrol(&map, &guard) {
+        return Err("The guard never leaves the map".into());
+    }
+
+    let mut guard_pos = guard.start;
+    let mut guard_dir = guard.heading;
 
     while map.guard_is_in_map(guard_pos) {
         _ = map.visited.insert(guard_pos);
 
-        let new_pos = &guard_dir.move_towards_direction(guard_pos);
+        let new_pos = &guard_dir.step(guard_pos);
 
         if map.obstructions.contains(&new_pos) {
-            guard_dir = guard_dir.rotate();
+            guard_dir = guard_dir.turn_clockwise();
         } else {
             guard_pos = *new_pos;
         }
@@ -90,35 +93,37 @@ fn part1(mut map: Map) -> Result<Map> {
     return Ok(map);
 }
 
-fn part2(map: Map) -> Result<()> {
-    let mut viable_blocks = 0;
-    for block in &map.visited {
-        if *block == map.guard_start {
-            continue;
-        }
-
-        let mut loop_visit = HashSet::new();
-        let mut guard_pos = map.guard_start;
-        let mut guard_dir = Dir::North;
-        while map.guard_is_in_map(guard_pos) {
+fn part2(map: &Map) -> Result<()> {
+    println!("Part 2 answer: {}", jump::count_loop_blocks(map, &map.first_guard()?));
+    Ok(())
+}
 
-            if !loop_visit.insert((guard_pos, guard_dir.clone())) {
-                viable_blocks += 1;
-                break;
-            }
-    
-            let new_pos = &guard_dir.move_towards_direction(guard_pos);
-    
-            if  new_pos == block || map.obstructions.contains(&new_pos) {
-                guard_dir = guard_dir.rotate();
-            } else {
-                guard_pos = *new_pos;
+/// One line per guard: where it exits, or how long its loop is.
+fn report(map: &Map, patrols: &[Patrol]) -> String {
+    let mut out = String::new();
+    for (idx, (guard, patrol)) in map.guards.iter().zip(patrols).enumerate() {
+        out.push_str(&format!("Guard {} at {},{} facing {}: ", idx + 1, guard.start.0, guard.start.1, heading_name(guard.heading)));
+        if let Some(other) = patrol.joined {
+            out.push_str(&format!("joins the route of guard {}, ", other + 1));
+        }
+        match patrol.outcome {
+            Outcome::Exits { at, heading, steps } => {
+                out.push_str(&format!("exits at {},{} heading {} after {} steps\n", at.0, at.1, heading_name(heading), steps));
             }
+            Outcome::Loops { steps, cycle: 0 } => out.push_str(&format!("turns in place forever after {} steps\n", steps)),
+            Outcome::Loops { steps, cycle } => out.push_str(&format!("loops after {} steps, {} steps per round\n", steps, cycle)),
         }
     }
+    out
+}
 
-    println!("Part 2 answer: {}", viable_blocks);
-    return Ok(());
+fn heading_name(heading: Direction) -> &'static str {
+    match heading {
+        Direction::North => "north",
+        Direction::East => "east",
+        Direction::South => "south",
+        Direction::West => "west",
+    }
 }
 
 /*
@@ -0,0 +1,187 @@
+use std::fs;
+
+pub use aoc_core::{Error, Result};
+use aoc_core::parsing::{numbers, parse_at, split_pair};
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Equation {
+    target: usize,
+    nums: Vec<usize>,
+}
+
+impl Equation {
+    /// `target: n n ...`, one equation per line. Numbers too large for usize are errors.
+    fn parse(line: &str, line_no: usize) -> Result<Equation> {
+        let (left, right) = split_pair(line, ": ", line_no)?;
+        let target = parse_at::<usize>(left, line_no)?;
+        let nums = numbers::<usize>(right, line_no)?;
+        if nums.is_empty() {
+            return Err(format!("line {}: no numbers after the test value", line_no).into());
+        }
+        Ok(Equation { target, nums })
+    }
+
+    const OPERATIONS_1: [fn (usize, usize) -> Option<usize>; 2] = [
+        |a, b| -> Option<usize> { a.checked_add(b) },
+        |a, b| -> Option<usize> { a.checked_mul(b) },
+    ];
+
+    const OPERATIONS_2: [fn (usize, usize) -> Option<usize>; 3] = [
+        |a, b| -> Option<usize> { a.checked_add(b) },
+        |a, b| -> Option<usize> { a.checked_mul(b) },
+        |a, b| -> Option<usize> { Equation::concat(a, b) },
+    ];
+
+    fn concat(a: usize, b: usize) -> Option<usize> {
+        let shift = 10usize.checked_pow(b.checked_ilog10().unwrap_or(0) + 1)?;
+        a.checked_mul(shift)?.checked_add(b)
+    }
+
+    fn can_solve(&self, ops: &[fn (usize, usize) -> Option<usize>]) -> usize {
+        match Equation::eval(self.target, self.nums[0], &self.nums[1..], ops) {
+            true => self.target,
+            false => 0,
+        }
+    }
+
+    /// Operators never make `acc` smaller, except multiplying by 0, so once it overflows or
+    /// passes the target that branch can't reach the target any more.
+    fn eval(target: usize, acc: usize, numbers: &[usize], ops: &[fn (usize, usize) -> Option<usize>]) -> bool {
+        if numbers.is_empty() {
+            return acc == target;
+        }
+        if acc > target && !numbers.contains(&0) {
+            return false;
+        }
+
+        ops.iter().any(|op| match op(acc, numbers[0]) {
+            Some(next) => Equation::eval(target, next, &numbers[1..], ops),
+            None => false,
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
+    let equations = get_equations()?;
+
+    part1(&equations)?;
+    part2(&equations)
+}
+
+fn get_equations() -> Result<Vec<Equation>> {
+    let input = fs::read_to_string("input.txt")?;
+    input.lines().enumerate().map(|(idx, line)| Equation::parse(line, idx + 1)).collect()
+}
+
+fn part1(equations: &Vec<Equation>) -> Result<()> {
+    let mut result: usize = 0;
+    for eq in equations {
+        result = result.checked_add(eq.can_solve(&Equation::OPERATIONS_1)).ok_or("Sum of calibration results overflows usize")?;
+    }
+
+    println!("Part 1 answer: {}", result);
+    return Ok(());
+}
+
+fn part2(equations: &Vec<Equation>) -> Result<()> {
+    let mut result: usize = 0;
+    for eq in equations {
+        result = result.checked_add(eq.can_solve(&Equation::OPERATIONS_2)).ok_or("Sum of calibration results overflows usize")?;
+    }
+
+    println!("Part 2 answer: {}", result);
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflowing_branches_are_not_solutions() {
+        let eq = Equation::parse("10: 999 999 999 999 999 999 999", 1).unwrap();
+        assert_eq!(eq.can_solve(&Equation::OPERATIONS_1), 0);
+        assert_eq!(eq.can_solve(&Equation::OPERATIONS_2), 0);
+    }
+
+    #[test]
+    fn targets_near_usize_max_are_reached() {
+        let eq = Equation::parse(&format!("{}: {} 1", usize::MAX, usize::MAX - 1), 1).unwrap();
+        assert_eq!(eq.can_solve(&Equation::OPERATIONS_1), usize::MAX);
+
+        let eq = Equation::parse(&format!("{}: {} 5", usize::MAX, usize::MAX / 10), 1).unwrap();
+        assert_eq!(eq.can_solve(&Equation::OPERATIONS_2), usize::MAX);
+    }
+
+    #[test]
+    fn concat_past_usize_max_overflows() {
+        assert_eq!(Equation::concat(usize::MAX / 10, 5), Some(usize::MAX));
+        assert_eq!(Equation::concat(usize::MAX / 10, 6), None);
+        assert_eq!(Equation::concat(usize::MAX / 10, 15), None);
+    }
+
+    #[test]
+    fn numbers_past_usize_max_are_parse_errors() {
+        let eq = Equation::parse("18446744073709551615: 1 18446744073709551614", 1).unwrap();
+        assert_eq!((eq.target, eq.nums), (usize::MAX, vec![1, usize::MAX - 1]));
+
+        let err = Equation::parse("18446744073709551616: 1 2", 3).unwrap_err().to_string();
+        assert_eq!(err, "line 3: can't parse '18446744073709551616': number too large to fit in target type");
+        let err = Equation::parse("190: 10 99999999999999999999", 4).unwrap_err().to_string();
+        assert_eq!(err, "line 4: can't parse '99999999999999999999': number too large to fit in target type");
+    }
+
+    #[test]
+    fn malformed_equations_are_parse_errors() {
+        assert_eq!(Equation::parse("190 10 19", 2).unwrap_err().to_string(), "line 2: expected ': ' in '190 10 19'");
+        assert_eq!(Equation::parse("190: ", 5).unwrap_err().to_string(), "line 5: no numbers after the test value");
+    }
+}
+
+/*
+--- Day 7: Bridge Repair ---
+The Historians take you to a familiar rope bridge over a river in the middle of a jungle. The Chief isn't on this side of the bridge, though; maybe he's on the other side?
+
+When you go to cross the bridge, you notice a group of engineers trying to repair it. (Apparently, it breaks pretty frequently.) You won't be able to cross until it's fixed.
+
+You ask how long it'll take; the engineers tell you that it only needs final calibrations, but some young elephants were playing nearby and stole all the operators from their calibration equations! They could finish the calibrations if only someone could determine which test values could possibly be produced by placing any combination of operators into their calibration equations (your puzzle input).
+
+For example:
+
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+Each line represents a single equation. The test value appears before the colon on each line; it is your job to determine whether the remaining numbers can be combined with operators to produce the test value.
+
+Operators are always evaluated left-to-right, not according to precedence rules. Furthermore, numbers in the equations cannot be rearranged. Glancing into the jungle, you can see elephants holding two different types of operators: add (+) and multiply (*).
+
+Only three of the above equations can be made true by inserting operators:
+
+190: 10 19 has only one position that accepts an operator: between 10 and 19. Choosing + would give 29, but choosing * would give the test value (10 * 19 = 190).
+3267: 81 40 27 has two positions for operators. Of the four possible configurations of the operators, two cause the right side to match the test value: 81 + 40 * 27 and 81 * 40 + 27 both equal 3267 (when evaluated left-to-right)!
+292: 11 6 16 20 can be solved in exactly one way: 11 + 6 * 16 + 20.
+The engineers just need the total calibration result, which is the sum of the test values from just the equations that could possibly be true. In the above example, the sum of the test values for the three equations listed above is 3749.
+
+Determine which equations could possibly be true. What is their total calibration result?
+
+--- Part Two ---
+The engineers seem concerned; the total calibration result you gave them is nowhere close to being within safety tolerances. Just then, you spot your mistake: some well-hidden elephants are holding a third type of operator.
+
+The concatenation operator (||) combines the digits from its left and right inputs into a single number. For example, 12 || 345 would become 12345. All operators are still evaluated left-to-right.
+
+Now, apart from the three equations that could be made true using only addition and multiplication, the above example has three more equations that can be made true by inserting operators:
+
+156: 15 6 can be made true through a single concatenation: 15 || 6 = 156.
+7290: 6 8 6 15 can be made true using 6 * 8 || 6 * 15.
+192: 17 8 14 can be made true using 17 || 8 + 14.
+Adding up all six test values (the three that could be made before using only + and * plus the new three that can now be made by also using ||) produces the new total calibration result of 11387.
+
+Using your new knowledge of elephant hiding spots, determine which equations could possibly be true. What is their total calibration result?
+*/
\ No newline at end of file
@@ -1,7 +1,6 @@
 use std::{collections::{HashMap, HashSet}, fs};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 struct Map {
     freq_antennas: HashMap<char, HashSet<(i32, i32)>>,
@@ -66,6 +65,7 @@ impl Map {
 }
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     let map = get_map()?;
 
     _ = part1(&map);
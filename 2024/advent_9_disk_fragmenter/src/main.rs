@@ -1,15 +1,26 @@
 use std::{fs, str::FromStr};
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 #[derive(Clone)]
 struct Disk {
     files_list: Vec<(usize, usize, usize)>,
     gaps_list: Vec<(usize, usize)>,
+    size: usize,
 }
 
 impl Disk {
+    /// Draws the block layout like the puzzle text, one digit per block and `.` for free space.
+    fn render(files: &[(usize, usize, usize)], size: usize) -> String {
+        let mut blocks = vec!['.'; size];
+        for (id, pos, len) in files {
+            for block in &mut blocks[*pos..*pos + *len] {
+                *block = char::from_digit((*id % 10) as u32, 10).unwrap();
+            }
+        }
+        blocks.into_iter().collect()
+    }
+
     fn calc_checksum(files: Vec<(usize, usize, usize)>) -> usize {
         files.iter().map(|x| {
             (0..x.2).map(|y| (y + x.1) * x.0).sum::<usize>()
@@ -21,12 +32,12 @@ impl FromStr for Disk {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let mut disk = Disk { files_list: Vec::new(), gaps_list: Vec::new() };
+        let mut disk = Disk { files_list: Vec::new(), gaps_list: Vec::new(), size: 0 };
 
         let mut file_space: bool = true;
         let mut id = 0;
         let mut pos = 0;
-        for ch in s.chars() {
+        for ch in s.trim_end().chars() {
             let num = ch.to_string().parse().unwrap();
     
             if num > 0 {
@@ -43,22 +54,25 @@ impl FromStr for Disk {
             pos += num;
             file_space = !file_space;
         }
+        disk.size = pos;
     
         Ok(disk)
     }
 }
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     let input = fs::read_to_string("input.txt")?;
     let disk = Disk::from_str(&input)?;
+    let render = aoc_core::params::has_flag("--render");
 
-    _ = part1(disk.clone());
-    _ = part2(disk);
+    _ = part1(disk.clone(), render);
+    _ = part2(disk, render);
 
     Ok(())
 }
 
-fn part1(mut disk: Disk) -> Result<()> {
+fn part1(mut disk: Disk, render: bool) -> Result<()> {
     let mut moved_files = Vec::new();
     while let Some((id, pos_st, len)) = disk.files_list.pop() {
         for d in (0..len).rev() {
@@ -79,13 +93,17 @@ fn part1(mut disk: Disk) -> Result<()> {
         }
     }
 
+    if render {
+        println!("{}", Disk::render(&moved_files, disk.size));
+    }
+
     let checksum = Disk::calc_checksum(moved_files);
 
     println!("Part 1 answer: {}", checksum);
     return Ok(());
 }
 
-fn part2(mut disk: Disk) -> Result<()> {
+fn part2(mut disk: Disk, render: bool) -> Result<()> {
     let mut moved_files = Vec::new();
     while let Some((id, pos_st, len)) = disk.files_list.pop() {
         if let Some(g_idx) = disk.gaps_list.iter().position(|(g_pos, g_len)| *g_pos < pos_st && *g_len >= len) {
@@ -106,6 +124,10 @@ fn part2(mut disk: Disk) -> Result<()> {
         }
     }
 
+    if render {
+        println!("{}", Disk::render(&moved_files, disk.size));
+    }
+
     let checksum = Disk::calc_checksum(moved_files);
 
     println!("Part 2 answer: {}", checksum);
@@ -1,9 +1,9 @@
 use std::fs;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use aoc_core::{Error, Result};
 
 fn main() -> Result<()> {
+    aoc_core::Params::from_args(&[])?;
     _ = part1();
     _ = part2();
 
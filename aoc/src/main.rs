@@ -0,0 +1,154 @@
+mod puzzles;
+mod snapshots;
+
+use std::{env, fs, path::{Path, PathBuf}, process::Command};
+
+use aoc_core::Result;
+
+const USAGE: &str = "usage:
+    aoc list [--year <year>]
+    aoc run --year <year> --day <day> [--example <n>] [--param <name>=<value>]... [-- <solver args>]
+    aoc puzzles --year <year> [--answers]
+    aoc snapshots --year <year> [--day <day>]";
+
+/// A puzzle crate living at `<year>/advent_<day>_<name>`.
+struct Puzzle {
+    year: u32,
+    day: u32,
+    package: String,
+    dir: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    match args.first().map(String::as_str) {
+        Some("list") => list(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some("puzzles") => export_puzzles(&args[1..]),
+        Some("snapshots") => check_snapshots(&args[1..]),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn list(args: &[String]) -> Result<()> {
+    let year = flag_value(args, "--year")?;
+
+    for puzzle in find_puzzles()?.iter().filter(|p| year.is_none_or(|y| y == p.year)) {
+        println!("{} day {:>2}  {}", puzzle.year, puzzle.day, puzzle.package);
+    }
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let (args, solver_args) = match args.iter().position(|a| a == "--") {
+        Some(idx) => (&args[..idx], &args[idx + 1..]),
+        None => (args, &[][..]),
+    };
+    let year = flag_value(args, "--year")?.ok_or(USAGE)?;
+    let day = flag_value(args, "--day")?.ok_or(USAGE)?;
+    let params = args.windows(2).filter(|w| w[0] == "--param").flat_map(|w| w.iter());
+
+    let puzzles = find_puzzles()?;
+    let puzzle = puzzles.iter().find(|p| p.year == year && p.day == day)
+        .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+
+    let work_dir = match flag_value(args, "--example")? {
+        Some(n) => {
+            println!("--- {} day {}: {} (example {}) ---", puzzle.year, puzzle.day, puzzle.package, n);
+            example_dir(puzzle, n as usize)?
+        }
+        None => {
+            println!("--- {} day {}: {} ---", puzzle.year, puzzle.day, puzzle.package);
+            puzzle.dir.clone()
+        }
+    };
+
+    let status = solver(puzzle, &work_dir).args(params).args(solver_args).status()?;
+
+    if !status.success() {
+        return Err(format!("{} exited with {}", puzzle.package, status).into());
+    }
+
+    Ok(())
+}
+
+/// `cargo run` for a solver, leaving room for its arguments.
+fn solver(puzzle: &Puzzle, work_dir: &Path) -> Command {
+    let mut command = Command::new(cargo());
+    command.args(["run", "--quiet", "--manifest-path"])
+        .arg(puzzles::manifest(&puzzle.dir))
+        .arg("--")
+        .current_dir(work_dir);
+    command
+}
+
+/// Examples run in a scratch directory so the solver picks them up as its input.txt
+fn example_dir(puzzle: &Puzzle, n: usize) -> Result<PathBuf> {
+    let dir = env::temp_dir().join(format!("aoc-{}-example-{}", puzzle.package, n));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("input.txt"), puzzles::example(puzzle, n)?)?;
+    Ok(dir)
+}
+
+fn export_puzzles(args: &[String]) -> Result<()> {
+    let year = flag_value(args, "--year")?.ok_or(USAGE)?;
+    let with_answers = args.iter().any(|a| a == "--answers");
+
+    let puzzles = find_puzzles()?;
+    let event = puzzles.iter().filter(|p| p.year == year).collect::<Vec<_>>();
+
+    puzzles::export(&event, with_answers)
+}
+
+fn check_snapshots(args: &[String]) -> Result<()> {
+    let year = flag_value(args, "--year")?.ok_or(USAGE)?;
+    let day = flag_value(args, "--day")?;
+
+    let puzzles = find_puzzles()?;
+    let event = puzzles.iter().filter(|p| p.year == year).collect::<Vec<_>>();
+
+    snapshots::check(&event, day)
+}
+
+fn cargo() -> String {
+    env::var("CARGO").unwrap_or("cargo".to_string())
+}
+
+fn flag_value(args: &[String], flag: &str) -> Result<Option<u32>> {
+    match args.iter().position(|a| a == flag) {
+        Some(idx) => {
+            let value = args.get(idx + 1).ok_or_else(|| format!("{} needs a value", flag))?;
+            Ok(Some(value.parse().map_err(|e| format!("{} {}: {}", flag, value, e))?))
+        }
+        None => Ok(None),
+    }
+}
+
+fn find_puzzles() -> Result<Vec<Puzzle>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().ok_or("Workspace root not found")?;
+
+    let mut puzzles = Vec::new();
+    for event in fs::read_dir(root)? {
+        let event = event?;
+        let Ok(year) = event.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+
+        for day_dir in fs::read_dir(event.path())? {
+            let day_dir = day_dir?;
+            let package = day_dir.file_name().to_string_lossy().to_string();
+            let day = package.strip_prefix("advent_")
+                .and_then(|rest| rest.split('_').next())
+                .and_then(|day| day.parse::<u32>().ok());
+
+            if let Some(day) = day {
+                puzzles.push(Puzzle { year, day, package, dir: day_dir.path() });
+            }
+        }
+    }
+    puzzles.sort_by_key(|p| (p.year, p.day));
+
+    Ok(puzzles)
+}
@@ -0,0 +1,235 @@
+use std::{fs, path::{Path, PathBuf}, process::Command};
+
+use aoc_core::Result;
+
+use crate::Puzzle;
+
+/// Where the Markdown archive of an event lives, e.g. `2024/puzzles`.
+pub fn archive_dir(puzzle: &Puzzle) -> PathBuf {
+    puzzle.dir.with_file_name("puzzles")
+}
+
+pub fn markdown_path(puzzle: &Puzzle) -> PathBuf {
+    archive_dir(puzzle).join(format!("day{:02}.md", puzzle.day))
+}
+
+/// Writes `dayNN.md` for every puzzle plus a `README.md` index linking them.
+pub fn export(puzzles: &[&Puzzle], with_answers: bool) -> Result<()> {
+    let Some(first) = puzzles.first() else {
+        return Err("No puzzles to export".into());
+    };
+    let dir = archive_dir(first);
+    fs::create_dir_all(&dir)?;
+
+    let mut index = String::from("# Puzzle archive\n\n");
+    index.push_str("| Day | Puzzle | Solver | Tests | Input | Part 1 | Part 2 |\n");
+    index.push_str("|----:|--------|--------|-------|-------|--------|--------|\n");
+
+    for puzzle in puzzles {
+        let source = fs::read_to_string(puzzle.dir.join("src").join("main.rs"))?;
+        let description = extract_description(&source)
+            .ok_or_else(|| format!("{} has no puzzle description comment", puzzle.package))?;
+        let (title, markdown) = to_markdown(description, puzzle);
+        fs::write(markdown_path(puzzle), markdown)?;
+
+        let (part1, part2) = match with_answers {
+            true => known_answers(puzzle),
+            false => (String::new(), String::new()),
+        };
+        let tests = test_files(puzzle)?.iter()
+            .map(|file| format!("[{}](../{}/src/{})", file, puzzle.package, file))
+            .collect::<Vec<_>>();
+        let tests = match tests.is_empty() {
+            true => "–".to_string(),
+            false => tests.join(", "),
+        };
+        index.push_str(&format!(
+            "| {} | [{}](day{:02}.md) | [main.rs](../{}/src/main.rs) | {} | [input.txt](../{}/input.txt) | {} | {} |\n",
+            puzzle.day, title, puzzle.day, puzzle.package, tests, puzzle.package, part1, part2));
+        println!("Wrote {}", markdown_path(puzzle).display());
+    }
+
+    fs::write(dir.join("README.md"), index)?;
+    println!("Wrote {}", dir.join("README.md").display());
+    Ok(())
+}
+
+/// Source files of a solver that hold a `#[cfg(test)]` module.
+fn test_files(puzzle: &Puzzle) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(puzzle.dir.join("src"))? {
+        let entry = entry?;
+        if fs::read_to_string(entry.path())?.contains("#[cfg(test)]") {
+            files.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the `n`-th (1-based) fenced example of a puzzle's Markdown file.
+pub fn example(puzzle: &Puzzle, n: usize) -> Result<String> {
+    let path = markdown_path(puzzle);
+    let markdown = fs::read_to_string(&path)
+        .map_err(|e| format!("{}: {} (run `aoc puzzles --year {}` first)", path.display(), e, puzzle.year))?;
+
+    let examples = fenced_blocks(&markdown);
+    let count = examples.len();
+    examples.into_iter().nth(n.wrapping_sub(1))
+        .ok_or_else(|| format!("Day {} has {} examples, there is no example {}", puzzle.day, count, n).into())
+}
+
+/// The puzzle text is kept in the last `/* ... */` block of each solver.
+fn extract_description(source: &str) -> Option<&str> {
+    let start = source.rfind("/*")?;
+    let end = start + source[start..].find("*/")?;
+    Some(source[start + 2..end].trim_matches(|c: char| c.is_whitespace()))
+}
+
+fn to_markdown(description: &str, puzzle: &Puzzle) -> (String, String) {
+    let mut title = puzzle.package.clone();
+    let mut out = String::new();
+    let mut in_code = false;
+
+    let lines = description.lines().map(str::trim_end).collect::<Vec<_>>();
+    for (idx, line) in lines.iter().copied().enumerate() {
+        if line.is_empty() {
+            // Blank lines inside an example are kept, e.g. between day 15's map and moves
+            let next = lines[idx..].iter().find(|l| !l.is_empty());
+            if in_code && next.is_some_and(|l| is_example_line(l)) {
+                out.push('\n');
+            }
+            continue;
+        }
+
+        let code = is_example_line(line);
+        if in_code && !code {
+            out.push_str("```\n\n");
+        }
+
+        if let Some(heading) = line.strip_prefix("--- ").and_then(|l| l.strip_suffix(" ---")) {
+            if heading.starts_with("Day ") {
+                title = heading.split_once(": ").map_or(heading, |(_, t)| t).to_string();
+                out.push_str(&format!("# {}\n\n", heading));
+            } else {
+                out.push_str(&format!("## {}\n\n", heading));
+            }
+        } else if code {
+            if !in_code {
+                out.push_str("```text\n");
+            }
+            out.push_str(line);
+            out.push('\n');
+        } else {
+            out.push_str(line.trim());
+            out.push_str("\n\n");
+        }
+        in_code = code;
+    }
+    if in_code {
+        out.push_str("```\n\n");
+    }
+
+    out.push_str("---\n\n");
+    out.push_str(&format!("[Solver](../{}/src/main.rs) · [Input](../{}/input.txt) · [Index](README.md)\n",
+        puzzle.package, puzzle.package));
+
+    (title, out)
+}
+
+/// Examples are grids or number lists, prose always has a few lowercase words.
+fn is_example_line(line: &str) -> bool {
+    if line.starts_with("--- ") {
+        return false;
+    }
+
+    let mut words = line.split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| word.len() >= 3 && word.chars().skip(1).all(|c| c.is_ascii_lowercase()));
+    let sentence_end = line.ends_with(['.', '!', '?']);
+
+    !words.any(|word| sentence_end || word.starts_with(|c: char| c.is_ascii_lowercase()))
+}
+
+fn fenced_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (&mut current, line.starts_with("```")) {
+            (None, true) => current = Some(String::new()),
+            (Some(_), true) => blocks.extend(current.take()),
+            (Some(block), false) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, false) => {}
+        }
+    }
+
+    blocks
+}
+
+/// Runs the solver on its committed `input.txt` and collects the printed answers.
+fn known_answers(puzzle: &Puzzle) -> (String, String) {
+    let output = Command::new(crate::cargo())
+        .args(["run", "--quiet", "--release", "--manifest-path"])
+        .arg(manifest(&puzzle.dir))
+        .current_dir(&puzzle.dir)
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        _ => return ("–".to_string(), "–".to_string()),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut answers = stdout.lines().filter_map(|l| l.split_once(" answer: ").map(|(_, a)| format!("`{}`", a)));
+    (answers.next().unwrap_or_default(), answers.next().unwrap_or_default())
+}
+
+pub fn manifest(dir: &Path) -> PathBuf {
+    dir.join("Cargo.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle() -> Puzzle {
+        Puzzle { year: 2024, day: 5, package: "advent_5_print_queue".to_string(), dir: PathBuf::from("2024/advent_5_print_queue") }
+    }
+
+    const DESCRIPTION: &str = "--- Day 5: Print Queue ---
+The first section specifies the page ordering rules.
+
+For example:
+
+47|53
+97|13
+
+75,47,61
+97,61,53
+
+The first update is in the right order.";
+
+    #[test]
+    fn blank_lines_inside_examples_are_kept() {
+        let (title, markdown) = to_markdown(DESCRIPTION, &puzzle());
+        assert_eq!(title, "Print Queue");
+        assert_eq!(fenced_blocks(&markdown), vec!["47|53\n97|13\n\n75,47,61\n97,61,53\n".to_string()]);
+    }
+
+    #[test]
+    fn prose_is_not_an_example() {
+        let (_, markdown) = to_markdown(DESCRIPTION, &puzzle());
+        assert!(markdown.starts_with("# Day 5: Print Queue\n\nThe first section specifies the page ordering rules.\n\nFor example:\n\n```text\n"));
+        assert!(markdown.contains("```\n\nThe first update is in the right order.\n\n"));
+    }
+
+    #[test]
+    fn description_is_the_last_comment() {
+        let source = "/* not this */\nfn main() {}\n/*\n--- Day 1 ---\nText.\n*/\n";
+        assert_eq!(extract_description(source), Some("--- Day 1 ---\nText."));
+    }
+}
@@ -0,0 +1,158 @@
+use std::fs;
+
+use aoc_core::Result;
+
+use crate::{puzzles, Puzzle};
+
+/// One line of `<event>/puzzles/snapshots.txt`:
+/// `<day> <example> <drawing>[:<first line>-<last line>] [-- <solver args>]`.
+/// The solver runs on the example block and its output has to contain the drawing block.
+struct Snapshot {
+    day: u32,
+    example: usize,
+    drawing: usize,
+    lines: Option<(usize, usize)>,
+    args: Vec<String>,
+}
+
+impl Snapshot {
+    fn parse(line: &str, line_no: usize) -> Result<Snapshot> {
+        let (spec, args) = line.split_once(" -- ").unwrap_or((line, ""));
+        let fields = spec.split_whitespace().collect::<Vec<_>>();
+        let [day, example, drawing] = fields[..] else {
+            return Err(format!("snapshots.txt line {}: expected '<day> <example> <drawing>'", line_no).into());
+        };
+
+        let (drawing, lines) = match drawing.split_once(':') {
+            Some((block, range)) => {
+                let (first, last) = aoc_core::parsing::split_pair(range, "-", line_no)?;
+                (block, Some((aoc_core::parsing::parse_at(first, line_no)?, aoc_core::parsing::parse_at(last, line_no)?)))
+            }
+            None => (drawing, None),
+        };
+
+        Ok(Snapshot {
+            day: aoc_core::parsing::parse_at(day, line_no)?,
+            example: aoc_core::parsing::parse_at(example, line_no)?,
+            drawing: aoc_core::parsing::parse_at(drawing, line_no)?,
+            lines,
+            args: args.split_whitespace().map(String::from).collect(),
+        })
+    }
+
+    fn expected(&self, puzzle: &Puzzle) -> Result<Vec<String>> {
+        let block = puzzles::example(puzzle, self.drawing)?;
+        let lines = block.lines().map(String::from).collect::<Vec<_>>();
+
+        match self.lines {
+            Some((first, last)) => lines.get(first.saturating_sub(1)..last).map(<[String]>::to_vec)
+                .ok_or_else(|| format!("Block {} has no lines {}-{}", self.drawing, first, last).into()),
+            None => Ok(lines),
+        }
+    }
+}
+
+pub fn check(puzzles: &[&Puzzle], day: Option<u32>) -> Result<()> {
+    let Some(first) = puzzles.first() else {
+        return Err("No puzzles to check".into());
+    };
+    let path = puzzles::archive_dir(first).join("snapshots.txt");
+    let specs = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let mut checked = 0;
+    let mut failed = 0;
+    for (idx, line) in specs.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let snapshot = Snapshot::parse(line, idx + 1)?;
+        if day.is_some_and(|d| d != snapshot.day) {
+            continue;
+        }
+        let puzzle = puzzles.iter().find(|p| p.day == snapshot.day)
+            .ok_or_else(|| format!("snapshots.txt line {}: no solution for day {}", idx + 1, snapshot.day))?;
+
+        let expected = snapshot.expected(puzzle)?;
+        let output = crate::solver(puzzle, &crate::example_dir(puzzle, snapshot.example)?)
+            .args(&snapshot.args)
+            .output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let actual = stdout.lines().collect::<Vec<_>>();
+
+        checked += 1;
+        let name = format!("day {} example {} -> block {}", snapshot.day, snapshot.example, snapshot.drawing);
+        if output.status.success() && contains_drawing(&actual, &expected) {
+            println!("ok    {}", name);
+        } else {
+            failed += 1;
+            println!("FAIL  {}", name);
+            if !output.status.success() {
+                println!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            print!("{}", diff(&actual, &expected));
+        }
+    }
+
+    println!("{} snapshots checked, {} failed", checked, failed);
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} snapshots failed", failed).into()),
+    }
+}
+
+fn contains_drawing(actual: &[&str], expected: &[String]) -> bool {
+    expected.is_empty() || actual.windows(expected.len()).any(|w| w.iter().zip(expected).all(|(a, e)| a == e))
+}
+
+/// Lines the drawing against the closest matching stretch of output, `-` expected, `+` actual.
+fn diff(actual: &[&str], expected: &[String]) -> String {
+    let matching = |start: usize| expected.iter().enumerate()
+        .filter(|(i, e)| actual.get(start + i).is_some_and(|a| a == e))
+        .count();
+    let start = (0..actual.len().max(1)).max_by_key(|s| (matching(*s), usize::MAX - s)).unwrap_or(0);
+
+    let mut out = String::new();
+    for (i, e) in expected.iter().enumerate() {
+        match actual.get(start + i) {
+            Some(a) if a == e => out.push_str(&format!("      {}\n", e)),
+            Some(a) => out.push_str(&format!("    - {}\n    + {}\n", e, a)),
+            None => out.push_str(&format!("    - {}\n", e)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_snapshot_lines() {
+        let snapshot = Snapshot::parse("15 7 8:100-106 -- --render --param seconds=0", 1).unwrap();
+        assert_eq!((snapshot.day, snapshot.example, snapshot.drawing, snapshot.lines), (15, 7, 8, Some((100, 106))));
+        assert_eq!(snapshot.args, ["--render", "--param", "seconds=0"]);
+        assert!(Snapshot::parse("15 7", 3).is_err());
+    }
+
+    #[test]
+    fn drawing_has_to_appear_in_order() {
+        let expected = vec!["#.#".to_string(), "...".to_string()];
+        assert!(contains_drawing(&["Step 1", "#.#", "...", "Part 1 answer: 3"], &expected));
+        assert!(!contains_drawing(&["#.#", "Step 1", "..."], &expected));
+    }
+
+    #[test]
+    fn diff_marks_differing_lines() {
+        let expected = vec!["#.#".to_string(), "...".to_string()];
+        assert_eq!(diff(&["Step 1", "#.#", ".O."], &expected), "      #.#\n    - ...\n    + .O.\n");
+    }
+
+    /// Runs every solver listed in `2024/puzzles/snapshots.txt` on its example.
+    #[test]
+    fn event_2024_matches_puzzle_drawings() {
+        let puzzles = crate::find_puzzles().unwrap();
+        let event = puzzles.iter().filter(|p| p.year == 2024).collect::<Vec<_>>();
+        check(&event, None).unwrap();
+    }
+}
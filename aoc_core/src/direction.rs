@@ -0,0 +1,75 @@
+/// Grid heading. Coordinates are `(row, col)`, so North decreases the row.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+
+    pub fn step(&self, pos: (i32, i32)) -> (i32, i32) {
+        let delta = self.delta();
+        (pos.0 + delta.0, pos.1 + delta.1)
+    }
+
+    pub fn turn_clockwise(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn from_char(ch: char) -> Option<Direction> {
+        match ch {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn north_decreases_the_row() {
+        let pos = (5, 7);
+        let steps = Direction::ALL.map(|dir| dir.step(pos));
+        assert_eq!(steps, [(4, 7), (5, 8), (6, 7), (5, 6)]);
+    }
+
+    #[test]
+    fn turning_clockwise_goes_round_in_order() {
+        for (idx, dir) in Direction::ALL.iter().enumerate() {
+            assert_eq!(dir.turn_clockwise(), Direction::ALL[(idx + 1) % 4]);
+            let (d_row, d_col) = dir.delta();
+            // Clockwise in `(row, col)` coordinates maps (dr, dc) to (dc, -dr)
+            assert_eq!(dir.turn_clockwise().delta(), (d_col, -d_row));
+        }
+    }
+
+    #[test]
+    fn arrows_point_their_way() {
+        let arrows = ['^', '>', 'v', '<'].map(Direction::from_char);
+        assert_eq!(arrows, Direction::ALL.map(Some));
+        assert_eq!(Direction::from_char('.'), None);
+        assert_eq!(Direction::from_char('V'), None);
+    }
+}
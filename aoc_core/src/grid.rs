@@ -0,0 +1,80 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::Error;
+
+/// Character grid keyed by `(row, col)`, the layout most days parse their map into.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Grid {
+    pub cells: HashMap<(i32, i32), char>,
+    pub rows: i32,
+    pub cols: i32,
+}
+
+impl Grid {
+    pub fn get(&self, pos: (i32, i32)) -> Option<char> {
+        self.cells.get(&pos).copied()
+    }
+
+    pub fn in_bounds(&self, pos: (i32, i32)) -> bool {
+        pos.0 >= 0 && pos.0 < self.rows && pos.1 >= 0 && pos.1 < self.cols
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                out.push(self.get((row, col)).unwrap_or(' '));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl FromStr for Grid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid = Grid::default();
+        for (row, line) in s.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                grid.cells.insert((row as i32, col as i32), ch);
+                grid.cols = grid.cols.max(col as i32 + 1);
+            }
+            grid.rows = row as i32 + 1;
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rows_and_columns() {
+        let grid = "ab\ncd\nef\n".parse::<Grid>().unwrap();
+        assert_eq!((grid.rows, grid.cols), (3, 2));
+        assert_eq!(grid.get((0, 1)), Some('b'));
+        assert_eq!(grid.get((2, 0)), Some('e'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.render(), "ab\ncd\nef\n");
+    }
+
+    #[test]
+    fn short_lines_leave_gaps() {
+        let grid = "abc\nd\n".parse::<Grid>().unwrap();
+        assert_eq!((grid.rows, grid.cols), (2, 3));
+        assert!(grid.in_bounds((1, 2)));
+        assert_eq!(grid.get((1, 2)), None);
+        assert_eq!(grid.render(), "abc\nd  \n");
+    }
+
+    #[test]
+    fn bounds_are_rows_by_columns() {
+        let grid = "...\n...\n".parse::<Grid>().unwrap();
+        for (pos, inside) in [((0, 0), true), ((1, 2), true), ((-1, 0), false), ((0, -1), false), ((2, 0), false), ((0, 3), false)] {
+            assert_eq!(grid.in_bounds(pos), inside, "{:?}", pos);
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod direction;
+pub mod error;
+pub mod grid;
+pub mod params;
+pub mod parsing;
+pub mod sequence;
+
+pub use direction::Direction;
+pub use error::{Error, Result};
+pub use grid::Grid;
+pub use params::{Check, Param, Params};
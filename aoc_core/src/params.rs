@@ -0,0 +1,159 @@
+use std::{env, fmt::Display, str::FromStr};
+
+use crate::Result;
+
+/// True when a bare switch such as `--render` was passed to the solver.
+pub fn has_flag(flag: &str) -> bool {
+    env::args().skip(1).any(|a| a == flag)
+}
+
+/// The value following `flag`, e.g. `--highlight html`. Only the first one counts.
+pub fn value(flag: &str) -> Option<String> {
+    values(flag).into_iter().next()
+}
+
+/// The values following every `flag`, for options that can be repeated, e.g. `--word XMAS
+/// --word SAMX`.
+pub fn values(flag: &str) -> Vec<String> {
+    values_in(&env::args().skip(1).collect::<Vec<String>>(), flag)
+}
+
+fn values_in(args: &[String], flag: &str) -> Vec<String> {
+    args.windows(2).filter(|w| w[0] == flag).map(|w| w[1].clone()).collect()
+}
+
+/// Constraint a parameter value has to satisfy before a solver sees it.
+#[derive(Debug, Clone, Copy)]
+pub enum Check {
+    Integer,
+    NonNegative,
+    Positive,
+    OneOf(&'static [&'static str]),
+}
+
+impl Check {
+    fn validate(&self, value: &str) -> std::result::Result<(), String> {
+        let number = || value.parse::<i128>().map_err(|e| e.to_string());
+        match self {
+            Check::Integer => number().map(|_| ()),
+            Check::NonNegative if number()? >= 0 => Ok(()),
+            Check::NonNegative => Err("must not be negative".to_string()),
+            Check::Positive if number()? > 0 => Ok(()),
+            Check::Positive => Err("must be positive".to_string()),
+            Check::OneOf(choices) if choices.contains(&value) => Ok(()),
+            Check::OneOf(choices) => Err(format!("expected one of: {}", choices.join(", "))),
+        }
+    }
+}
+
+/// A tunable declared by a day, e.g. `Param::new("blinks", "25", Check::Positive)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub check: Check,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, check: Check) -> Param {
+        Param { name, default, check }
+    }
+}
+
+/// Parameter values after applying `--param name=value` overrides to the declared defaults.
+#[derive(Debug, Clone)]
+pub struct Params {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Params {
+    pub fn from_args(declared: &[Param]) -> Result<Params> {
+        Params::parse(declared, &env::args().skip(1).collect::<Vec<String>>())
+    }
+
+    pub fn parse(declared: &[Param], args: &[String]) -> Result<Params> {
+        let mut values = declared.iter().map(|p| (p.name, p.default.to_string())).collect::<Vec<_>>();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg != "--param" {
+                continue;
+            }
+
+            let pair = args.next().ok_or("--param needs a name=value pair")?;
+            let (name, value) = pair.split_once('=').ok_or_else(|| format!("--param {}: expected name=value", pair))?;
+            let Some(param) = declared.iter().find(|p| p.name == name) else {
+                if declared.is_empty() {
+                    return Err(format!("Unknown parameter '{}', this solver has no parameters", name).into());
+                }
+                let known = declared.iter().map(|p| p.name).collect::<Vec<_>>().join(", ");
+                return Err(format!("Unknown parameter '{}', expected one of: {}", name, known).into());
+            };
+            param.check.validate(value).map_err(|e| format!("Parameter {}={}: {}", name, value, e))?;
+
+            if let Some(entry) = values.iter_mut().find(|(n, _)| *n == name) {
+                entry.1 = value.to_string();
+            }
+        }
+
+        Ok(Params { values })
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (_, value) = self.values.iter().find(|(n, _)| *n == name)
+            .ok_or_else(|| format!("Parameter '{}' is not declared", name))?;
+        value.parse::<T>().map_err(|e| format!("Parameter {}={}: {}", name, value, e).into())
+    }
+
+    /// Records the values in use so answers can be traced back to their settings.
+    pub fn print(&self) {
+        if self.values.is_empty() {
+            return;
+        }
+
+        let values = self.values.iter().map(|(n, v)| format!("{}={}", n, v)).collect::<Vec<_>>();
+        println!("Parameters: {}", values.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: [Param; 1] = [Param::new("steps", "10", Check::Positive)];
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn repeated_values_keep_their_order() {
+        let args = args(&["--word", "XMAS", "--render", "--word", "SAMX", "--word"]);
+        assert_eq!(values_in(&args, "--word"), ["XMAS", "SAMX"]);
+        assert!(values_in(&args, "--shape").is_empty());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let params = Params::parse(&DECLARED, &args(&["--render", "--param", "steps=3"])).unwrap();
+        assert_eq!(params.get::<u32>("steps").unwrap(), 3);
+        assert_eq!(Params::parse(&DECLARED, &[]).unwrap().get::<u32>("steps").unwrap(), 10);
+    }
+
+    #[test]
+    fn values_are_checked() {
+        assert!(Params::parse(&DECLARED, &args(&["--param", "steps=0"])).is_err());
+        assert!(Params::parse(&DECLARED, &args(&["--param", "steps"])).is_err());
+    }
+
+    #[test]
+    fn unknown_parameters_are_rejected() {
+        assert!(Params::parse(&DECLARED, &args(&["--param", "size=3"])).is_err());
+        let err = Params::parse(&[], &args(&["--param", "foo=1"])).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown parameter 'foo', this solver has no parameters");
+    }
+}
@@ -0,0 +1,49 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::Result;
+
+/// Parses `token` and names the offending line in the error instead of panicking.
+pub fn parse_at<T>(token: &str, line_no: usize) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token.trim().parse::<T>().map_err(|e| format!("line {}: can't parse '{}': {}", line_no, token, e).into())
+}
+
+/// Parses every whitespace separated token of `line`.
+pub fn numbers<T>(line: &str, line_no: usize) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    line.split_whitespace().map(|token| parse_at(token, line_no)).collect()
+}
+
+pub fn split_pair<'a>(line: &'a str, delimiter: &str, line_no: usize) -> Result<(&'a str, &'a str)> {
+    line.split_once(delimiter).ok_or_else(|| format!("line {}: expected '{}' in '{}'", line_no, delimiter, line).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_trimmed_and_errors_name_the_line() {
+        assert_eq!(parse_at::<u8>(" 42 ", 1).unwrap(), 42);
+        assert_eq!(parse_at::<u8>("256", 7).unwrap_err().to_string(), "line 7: can't parse '256': number too large to fit in target type");
+    }
+
+    #[test]
+    fn numbers_split_on_any_whitespace() {
+        assert_eq!(numbers::<i32>("1  -2\t3", 1).unwrap(), [1, -2, 3]);
+        assert!(numbers::<i32>("", 1).unwrap().is_empty());
+        assert_eq!(numbers::<i32>("1 2x", 4).unwrap_err().to_string(), "line 4: can't parse '2x': invalid digit found in string");
+    }
+
+    #[test]
+    fn pairs_split_at_the_first_delimiter() {
+        assert_eq!(split_pair("a|b|c", "|", 1).unwrap(), ("a", "b|c"));
+        assert_eq!(split_pair("a,b", "|", 3).unwrap_err().to_string(), "line 3: expected '|' in 'a,b'");
+    }
+}
@@ -0,0 +1,180 @@
+/// Which way a single step between two adjacent values goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slope {
+    Increasing,
+    Decreasing,
+    Flat,
+}
+
+impl Slope {
+    pub fn of(prev: i32, next: i32) -> Slope {
+        match next.cmp(&prev) {
+            std::cmp::Ordering::Greater => Slope::Increasing,
+            std::cmp::Ordering::Less => Slope::Decreasing,
+            std::cmp::Ordering::Equal => Slope::Flat,
+        }
+    }
+}
+
+/// Slope shared by every step, `None` when the steps disagree or there are none.
+pub fn slope(seq: &[i32]) -> Option<Slope> {
+    let mut steps = seq.windows(2).map(|w| Slope::of(w[0], w[1]));
+    let first = steps.next()?;
+    steps.all(|d| d == first).then_some(first)
+}
+
+/// A maximal stretch of values whose steps all go the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub len: usize,
+    pub slope: Slope,
+}
+
+/// Splits `seq` into monotone runs. Neighbouring runs share their boundary value, so
+/// `1 3 2` is an increasing run `1 3` followed by a decreasing run `3 2`.
+pub fn monotone_runs(seq: &[i32]) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for w in seq.windows(2) {
+        let slope = Slope::of(w[0], w[1]);
+        match runs.last_mut() {
+            Some(run) if run.slope == slope => run.len += 1,
+            _ => runs.push(Run { len: 2, slope }),
+        }
+    }
+    runs
+}
+
+/// Indices of the longest subsequence whose steps all go in one of `slopes` and pass
+/// `step_ok(slope, prev, next)`, dropping at most `max_removals` values when given.
+///
+/// `dropped[i]` is the fewest values skipped before `i` in a safe subsequence ending at `i`.
+/// With a removal limit `k` only the `k + 1` values before `i` can precede it, so the search
+/// is O(n·k) per slope, and O(n²) without a limit.
+pub fn safe_subsequence<F>(seq: &[i32], slopes: &[Slope], step_ok: F, max_removals: Option<usize>) -> Option<Vec<usize>>
+where
+    F: Fn(Slope, i32, i32) -> bool,
+{
+    let n = seq.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    let k = max_removals.unwrap_or(n).min(n);
+    let mut best: Option<Vec<usize>> = None;
+
+    for slope in slopes {
+        let mut dropped = vec![usize::MAX; n];
+        let mut from = vec![None; n];
+        for i in 0..n {
+            if i <= k {
+                dropped[i] = i;
+            }
+            for j in i.saturating_sub(k + 1)..i {
+                if dropped[j] == usize::MAX
+                    || Slope::of(seq[j], seq[i]) != *slope
+                    || !step_ok(*slope, seq[j], seq[i]) {
+                    continue;
+                }
+                let total = dropped[j] + (i - j - 1);
+                if total < dropped[i] {
+                    dropped[i] = total;
+                    from[i] = Some(j);
+                }
+            }
+        }
+
+        let last = (n.saturating_sub(k + 1)..n)
+            .filter(|i| dropped[*i] != usize::MAX && dropped[*i] + (n - 1 - i) <= k)
+            .min_by_key(|i| dropped[*i] + (n - 1 - i));
+        let Some(last) = last else {
+            continue;
+        };
+
+        let mut kept = Vec::new();
+        let mut curr = Some(last);
+        while let Some(i) = curr {
+            kept.push(i);
+            curr = from[i];
+        }
+        kept.reverse();
+
+        if best.as_ref().is_none_or(|b| kept.len() > b.len()) {
+            best = Some(kept);
+        }
+    }
+
+    best
+}
+
+pub fn longest_safe_subsequence<F>(seq: &[i32], slopes: &[Slope], step_ok: F) -> Vec<usize>
+where
+    F: Fn(Slope, i32, i32) -> bool,
+{
+    // A single value is always safe, so without a removal limit there is always an answer
+    safe_subsequence(seq, slopes, step_ok, None).unwrap_or_default()
+}
+
+/// How many values have to go before the rest is safe.
+pub fn min_removals<F>(seq: &[i32], slopes: &[Slope], step_ok: F) -> usize
+where
+    F: Fn(Slope, i32, i32) -> bool,
+{
+    seq.len() - longest_safe_subsequence(seq, slopes, step_ok).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOTH: [Slope; 2] = [Slope::Increasing, Slope::Decreasing];
+
+    /// Day 2's reports from the puzzle text, with steps of 1 to 3 in either direction.
+    const REPORTS: [[i32; 5]; 6] = [
+        [7, 6, 4, 2, 1],
+        [1, 2, 7, 8, 9],
+        [9, 7, 6, 2, 1],
+        [1, 3, 2, 4, 5],
+        [8, 6, 4, 4, 1],
+        [1, 3, 6, 7, 9],
+    ];
+
+    fn small_step(_: Slope, prev: i32, next: i32) -> bool {
+        (1..=3).contains(&prev.abs_diff(next))
+    }
+
+    #[test]
+    fn slopes_of_puzzle_reports() {
+        let slopes = REPORTS.map(|r| slope(&r));
+        assert_eq!(slopes, [Some(Slope::Decreasing), Some(Slope::Increasing), Some(Slope::Decreasing), None, None, Some(Slope::Increasing)]);
+        assert_eq!(slope(&[5]), None);
+    }
+
+    #[test]
+    fn puzzle_reports_without_removals() {
+        let safe = REPORTS.map(|r| safe_subsequence(&r, &BOTH, small_step, Some(0)).is_some());
+        assert_eq!(safe, [true, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn puzzle_reports_with_one_removal() {
+        let kept = REPORTS.map(|r| safe_subsequence(&r, &BOTH, small_step, Some(1)));
+        assert_eq!(kept[0], Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(kept[1], None);
+        assert_eq!(kept[2], None);
+        assert_eq!(kept[3].as_ref().map(Vec::len), Some(4));
+        assert_eq!(kept[4].as_ref().map(Vec::len), Some(4));
+        assert_eq!(kept[5], Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn minimum_removals_of_puzzle_reports() {
+        assert_eq!(REPORTS.map(|r| min_removals(&r, &BOTH, small_step)), [0, 2, 2, 1, 1, 0]);
+        assert_eq!(min_removals(&[], &BOTH, small_step), 0);
+    }
+
+    #[test]
+    fn runs_share_their_boundary() {
+        let runs = monotone_runs(&[1, 3, 2, 1, 1]);
+        let runs = runs.iter().map(|r| (r.slope, r.len)).collect::<Vec<_>>();
+        assert_eq!(runs, [(Slope::Increasing, 2), (Slope::Decreasing, 3), (Slope::Flat, 2)]);
+    }
+}